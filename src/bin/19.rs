@@ -0,0 +1,9 @@
+use advent_of_code::days::day19::{part_one, part_two, PARAMS};
+use advent_of_code::Params;
+
+fn main() {
+    let input = &advent_of_code::read_file("inputs", 19);
+    let params = &Params::from_args(PARAMS);
+    advent_of_code::solve!(1, part_one, input, params);
+    advent_of_code::solve!(2, part_two, input, params);
+}
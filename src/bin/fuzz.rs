@@ -0,0 +1,116 @@
+use std::process;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use advent_of_code::differential::save_input;
+use advent_of_code::fuzz;
+use advent_of_code::generators::{self, Day};
+use advent_of_code::helpers::Rng;
+use advent_of_code::Params;
+
+struct Args {
+    day: u8,
+    runs: usize,
+    seed: u64,
+    timeout: u64,
+}
+
+fn parse_args() -> Result<(Args, Params), pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let params = Params::from_arguments(&mut args);
+    let parsed = Args {
+        runs: args.opt_value_from_str("--runs")?.unwrap_or(10000),
+        seed: args.opt_value_from_str("--seed")?.unwrap_or(1),
+        timeout: args.opt_value_from_str("--timeout")?.unwrap_or(5),
+        day: args.free_from_str()?,
+    };
+    Ok((parsed, params))
+}
+
+enum Outcome {
+    Passed,
+    Crashed(String),
+    Hung,
+}
+
+/// Runs both parts on a worker thread, so an input that never finishes can be reported.
+fn check(day: Day, input: &str, params: &Params, timeout: Duration) -> Outcome {
+    let (sender, receiver) = mpsc::channel();
+    let (input, params) = (input.to_string(), params.clone());
+    thread::Builder::new()
+        .stack_size(64 * 1024 * 1024)
+        .spawn(move || sender.send(fuzz::run(&day, &input, &params)))
+        .expect("could not spawn a fuzzing thread");
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(())) => Outcome::Passed,
+        Ok(Err(crash)) => Outcome::Crashed(crash),
+        Err(_) => Outcome::Hung,
+    }
+}
+
+fn main() {
+    let (args, params) = match parse_args() {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Failed to parse arguments: {}", e);
+            eprintln!("example: `cargo fuzz 13 --runs 50000 --seed 7`");
+            process::exit(1);
+        }
+    };
+    let Some(day) = generators::day(args.day) else {
+        eprintln!("No solvers for day {} (is its feature enabled?)", args.day);
+        process::exit(1);
+    };
+    if let Err(error) = params.check(day.params) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+    // unless overridden, the expensive days get the small params the tests use.
+    let params = params.with_defaults(fuzz::params(&day));
+    let corpus = fuzz::corpus(args.day);
+    if corpus.is_empty() {
+        eprintln!("No seed corpus for day {} in src/examples", args.day);
+        process::exit(1);
+    }
+
+    fuzz::install_hook();
+    let timeout = Duration::from_secs(args.timeout);
+    let mut rng = Rng::new(args.seed);
+    for run in 0..corpus.len() + args.runs {
+        let input = match corpus.get(run) {
+            Some(seed) => seed.clone(),
+            None => fuzz::mutate(rng.choose(&corpus).clone().as_str(), &corpus, &mut rng),
+        };
+        if run > 0 && run % 1000 == 0 {
+            eprintln!("{} runs", run);
+        }
+        match check(day, &input, &params, timeout) {
+            Outcome::Passed => continue,
+            Outcome::Crashed(crash) => {
+                // candidates that hang are not the same crash, and are left running.
+                let input = fuzz::minimise(&input, &crash, |candidate| {
+                    match check(day, candidate, &params, timeout) {
+                        Outcome::Crashed(crash) => Some(crash),
+                        _ => None,
+                    }
+                });
+                let path = save_input(args.day, "crash", &input);
+                println!(
+                    "run {}: crashed with {}; saved to {}:\n{}",
+                    run, crash, path, input
+                );
+            }
+            Outcome::Hung => {
+                // the worker cannot be stopped, so the input is saved as it is.
+                let path = save_input(args.day, "crash", &input);
+                println!(
+                    "run {}: no answer after {:?}; saved to {}:\n{}",
+                    run, timeout, path, input
+                );
+            }
+        }
+        process::exit(1);
+    }
+    println!("{} runs without a crash", corpus.len() + args.runs);
+}
@@ -0,0 +1,109 @@
+use std::process;
+use std::time::{Duration, Instant};
+
+use advent_of_code::generators::{self, Day, Solver};
+use advent_of_code::helpers::Rng;
+use advent_of_code::{Params, ANSI_BOLD, ANSI_RESET};
+
+struct Args {
+    day: u8,
+    size: usize,
+    seed: u64,
+    scaling: bool,
+    steps: usize,
+}
+
+fn parse_args() -> Result<(Args, Params), pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let params = Params::from_arguments(&mut args);
+    let parsed = Args {
+        size: args.opt_value_from_str("--size")?.unwrap_or(10),
+        seed: args.opt_value_from_str("--seed")?.unwrap_or(1),
+        scaling: args.contains("--scaling"),
+        steps: args.opt_value_from_str("--steps")?.unwrap_or(6),
+        day: args.free_from_str()?,
+    };
+    Ok((parsed, params))
+}
+
+fn time(solver: Solver, input: &str, params: &Params) -> (Duration, bool) {
+    let timer = Instant::now();
+    let solved = solver(input, params).is_some();
+    (timer.elapsed(), solved)
+}
+
+fn cell((elapsed, solved): (Duration, bool)) -> String {
+    match solved {
+        true => format!("{:.2?}", elapsed),
+        false => format!("{:.2?} (none)", elapsed),
+    }
+}
+
+/// Times both parts on inputs of doubling size. The plot has one `#` per doubling of the total
+/// time in microseconds, so a linear solver adds one `#` per row and a quadratic one adds two.
+fn scaling(day: &Day, args: &Args, params: &Params) {
+    println!(
+        "{}{:>8} {:>10} {:>18} {:>18} {:>8}  plot{}",
+        ANSI_BOLD, "size", "bytes", "part 1", "part 2", "growth", ANSI_RESET
+    );
+    let mut previous: Option<Duration> = None;
+    for step in 0..args.steps {
+        let size = args.size << step;
+        let input = (day.generate)(size, &mut Rng::new(args.seed), params);
+        let part_one = time(day.part_one, &input, params);
+        let part_two = time(day.part_two, &input, params);
+        let total = part_one.0 + part_two.0;
+        let growth = match previous {
+            Some(previous) if !previous.is_zero() => {
+                format!("×{:.1}", total.as_secs_f64() / previous.as_secs_f64())
+            }
+            _ => String::new(),
+        };
+        let plot = "#".repeat((total.as_micros() as f64 + 1.0).log2().ceil() as usize);
+        println!(
+            "{:>8} {:>10} {:>18} {:>18} {:>8}  {}",
+            size,
+            input.len(),
+            cell(part_one),
+            cell(part_two),
+            growth,
+            plot
+        );
+        previous = Some(total);
+    }
+}
+
+fn main() {
+    let (args, params) = match parse_args() {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Failed to parse arguments: {}", e);
+            eprintln!("example: `cargo gen 12 --size 100 --seed 7` or `cargo gen 12 --scaling`");
+            process::exit(1);
+        }
+    };
+
+    let day = match generators::day(args.day) {
+        Some(day) => day,
+        None => {
+            eprintln!(
+                "No generator for day {} (is its feature enabled?)",
+                args.day
+            );
+            process::exit(1);
+        }
+    };
+
+    if let Err(error) = params.check(day.params) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+
+    match args.scaling {
+        true => scaling(&day, &args, &params),
+        false => print!(
+            "{}",
+            (day.generate)(args.size, &mut Rng::new(args.seed), &params)
+        ),
+    }
+}
@@ -0,0 +1,126 @@
+/*
+ * Checkpoints of long simulations, e.g. `cargo solve 23 -- --checkpoint-every 10000`, and after
+ * an interruption `cargo solve 23 -- --resume checkpoint-23-2.json`. Every N steps, the state of
+ * a part's simulation is written as JSON to `checkpoint-NN-P.json` (or `--param
+ * checkpoint=<file>`). Resuming picks the part the file is for up where it was saved.
+ */
+use std::fs;
+
+use serde_json::{json, Value};
+
+use crate::helpers::Simulation;
+use crate::{Param, Params};
+
+/// The checkpoint params, for the days that checkpoint to list with their own. The default
+/// path is `checkpoint-NN-P.json`.
+pub const EVERY: Param = Param::new::<usize>("checkpoint_every", "0");
+pub const PATH: Param = Param::new::<String>("checkpoint", "");
+pub const RESUME: Param = Param::new::<String>("resume", "");
+
+const PARAMS: &[Param] = &[EVERY, PATH, RESUME];
+
+/// A simulation that can be saved as JSON and loaded back into one of the same input.
+pub trait Checkpoint: Simulation {
+    fn save(&self) -> Value;
+
+    /// Puts back a state from `save`, or returns `None` if `state` is not one.
+    fn load(&mut self, state: &Value) -> Option<()>;
+}
+
+/// Steps `simulation` until it is over or has taken `limit` steps, resuming and checkpointing
+/// as `params` ask. Returns the steps taken, those before resuming included.
+pub fn run<S: Checkpoint>(
+    simulation: &mut S,
+    day: u8,
+    part: u8,
+    params: &Params,
+    limit: usize,
+) -> usize {
+    let every: usize = params.get(PARAMS, "checkpoint_every");
+    let mut path: String = params.get(PARAMS, "checkpoint");
+    if path.is_empty() {
+        path = format!("checkpoint-{:02}-{}.json", day, part);
+    }
+    let resume: String = params.get(PARAMS, "resume");
+
+    let mut steps = match resume.is_empty() {
+        true => 0,
+        false => resume_from(simulation, day, part, &resume).unwrap_or(0),
+    };
+    while steps < limit && simulation.step() {
+        steps += 1;
+        if every > 0 && steps.is_multiple_of(every) {
+            write(simulation, day, part, steps, &path);
+        }
+    }
+    steps
+}
+
+/// Loads the checkpoint at `path` into `simulation`, if it is one of the same part. Returns the
+/// steps it was taken after.
+fn resume_from<S: Checkpoint>(simulation: &mut S, day: u8, part: u8, path: &str) -> Option<usize> {
+    let checkpoint = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("could not read checkpoint {}: {}", path, e));
+    let checkpoint: Value = serde_json::from_str(&checkpoint)
+        .unwrap_or_else(|e| panic!("checkpoint {} is not JSON: {}", path, e));
+
+    if checkpoint["day"] != json!(day) {
+        panic!("checkpoint {} is not for day {}", path, day);
+    }
+    // the other part starts over.
+    if checkpoint["part"] != json!(part) {
+        return None;
+    }
+    let steps = checkpoint["steps"].as_u64();
+    match steps.and_then(|_| simulation.load(&checkpoint["state"])) {
+        Some(()) => steps.map(|steps| steps as usize),
+        None => panic!("checkpoint {} does not fit this input", path),
+    }
+}
+
+/// Writes a checkpoint next to `path` first, so an interruption never leaves half of one.
+fn write<S: Checkpoint>(simulation: &S, day: u8, part: u8, steps: usize, path: &str) {
+    let checkpoint = json!({
+        "day": day,
+        "part": part,
+        "steps": steps,
+        "state": simulation.save(),
+    });
+    let partial = format!("{}.partial", path);
+    fs::write(&partial, checkpoint.to_string())
+        .and_then(|_| fs::rename(&partial, path))
+        .unwrap_or_else(|e| panic!("could not write checkpoint {}: {}", path, e));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators;
+
+    #[test]
+    fn test_resume() {
+        for (number, every) in [(17, 500), (23, 4), (24, 5)] {
+            let Some(day) = generators::day(number) else {
+                continue;
+            };
+            let input = crate::read_file("examples", number);
+            let path = std::env::temp_dir().join(format!("checkpoint-{:02}-test.json", number));
+            let path = path.to_str().unwrap();
+            let params = Params::default().with("rocks_two", 3000);
+            let expected = (day.part_two)(&input, &params);
+
+            let checkpointed = params
+                .clone()
+                .with("checkpoint_every", every)
+                .with("checkpoint", path);
+            assert_eq!((day.part_two)(&input, &checkpointed), expected);
+            let checkpoint: Value =
+                serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+            assert!(checkpoint["steps"].as_u64() > Some(0), "day {}", number);
+
+            let resumed = params.with("resume", path);
+            assert_eq!((day.part_two)(&input, &resumed), expected, "day {}", number);
+            fs::remove_file(path).unwrap();
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::io::BufRead;
+
+use crate::helpers::{stream_lines, Rng};
+use crate::Params;
+use itertools::Itertools;
+
+pub fn part_one(input: &str) -> Option<u32> {
+    largest_group_sums(input.lines().map(Some), 1)?
+        .into_iter()
+        .max()
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    Some(
+        largest_group_sums(input.lines().map(Some), 3)?
+            .into_iter()
+            .sum(),
+    )
+}
+
+pub fn part_one_stream(input: impl BufRead) -> Option<u32> {
+    largest_group_sums(stream_lines(input), 1)?
+        .into_iter()
+        .max()
+}
+
+pub fn part_two_stream(input: impl BufRead) -> Option<u32> {
+    Some(
+        largest_group_sums(stream_lines(input), 3)?
+            .into_iter()
+            .sum(),
+    )
+}
+
+/// The `n` largest calorie totals, or `None` if a line is not a number. Only those `n` are
+/// kept, so memory does not grow with the number of elves.
+pub fn largest_group_sums<L: AsRef<str>>(
+    lines: impl Iterator<Item = Option<L>>,
+    n: usize,
+) -> Option<Vec<u32>> {
+    let keep = |largest: &mut Vec<u32>, sum: u32| {
+        largest.push(sum);
+        largest.sort_unstable_by(|a, b| b.cmp(a));
+        largest.truncate(n);
+    };
+
+    let mut largest = vec![];
+    let mut sum = 0_u32;
+    for line in lines {
+        match line?.as_ref() {
+            "" => keep(&mut largest, std::mem::take(&mut sum)),
+            calories_str => sum = sum.checked_add(calories_str.parse().ok()?)?,
+        }
+    }
+    keep(&mut largest, sum);
+    Some(largest)
+}
+
+/// Inventories of `size` elves, each carrying up to fifteen snacks.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            (0..rng.between(1, 15))
+                .map(|_| rng.between(1000, 60000).to_string())
+                .join("\n")
+        })
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 1);
+        assert_eq!(part_one(&input), Some(24000));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 1);
+        assert_eq!(part_two(&input), Some(45000));
+    }
+}
@@ -0,0 +1,127 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::helpers::{stream_lines, Rng};
+use crate::Params;
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, EnumString};
+
+pub fn part_one(input: &str) -> Option<usize> {
+    score_as_moves(input.lines().map(Some))
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    score_as_results(input.lines().map(Some))
+}
+
+pub fn part_one_stream(input: impl BufRead) -> Option<usize> {
+    score_as_moves(stream_lines(input))
+}
+
+pub fn part_two_stream(input: impl BufRead) -> Option<usize> {
+    score_as_results(stream_lines(input))
+}
+
+/// Total score when the second column is the move to play.
+fn score_as_moves<L: AsRef<str>>(lines: impl Iterator<Item = Option<L>>) -> Option<usize> {
+    lines
+        .map(|line| {
+            let (other_player, my_move) = parse_line(line?.as_ref(), |second_col_str| {
+                Move::from_str(second_col_str).ok()
+            })?;
+            Some(my_move as usize + game_result(&other_player, &my_move) as usize)
+        })
+        .sum()
+}
+
+/// Total score when the second column is the result to reach.
+fn score_as_results<L: AsRef<str>>(lines: impl Iterator<Item = Option<L>>) -> Option<usize> {
+    lines
+        .map(|line| {
+            let (other_player, result) = parse_line(line?.as_ref(), |second_col_str| {
+                Result::from_str(second_col_str).ok()
+            })?;
+            Some(find_my_move(&other_player, &result) as usize + result as usize)
+        })
+        .sum()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, EnumIter)]
+pub enum Move {
+    #[strum(serialize = "A", serialize = "X")]
+    Rock = 1,
+    #[strum(serialize = "B", serialize = "Y")]
+    Paper = 2,
+    #[strum(serialize = "C", serialize = "Z")]
+    Scissors = 3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum Result {
+    #[strum(serialize = "X")]
+    Lose = 0,
+    #[strum(serialize = "Y")]
+    Draw = 3,
+    #[strum(serialize = "Z")]
+    Win = 6,
+}
+
+pub fn parse_line<F: Fn(&str) -> Option<Sc>, Sc>(
+    line: &str,
+    second_col_mapping_fn: F,
+) -> Option<(Move, Sc)> {
+    let moves = line.split_whitespace().collect_vec();
+    match moves[..] {
+        [first, second] => Some((Move::from_str(first).ok()?, second_col_mapping_fn(second)?)),
+        _ => None,
+    }
+}
+
+pub fn game_result(other_move: &Move, my_move: &Move) -> Result {
+    if other_move == my_move {
+        return Result::Draw;
+    }
+
+    if (*my_move as usize) % 3 + 1 == *other_move as usize {
+        return Result::Lose;
+    }
+
+    Result::Win
+}
+
+pub fn find_my_move(other_move: &Move, result: &Result) -> Move {
+    Move::iter()
+        .find(|my_move| &game_result(other_move, my_move) == result)
+        .unwrap()
+}
+
+/// A strategy guide of `size` random rounds.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            format!(
+                "{} {}",
+                rng.choose(&['A', 'B', 'C']),
+                rng.choose(&['X', 'Y', 'Z'])
+            )
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 2);
+        assert_eq!(part_one(&input), Some(15));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 2);
+        assert_eq!(part_two(&input), Some(12));
+    }
+}
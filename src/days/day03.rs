@@ -0,0 +1,127 @@
+use crate::helpers::{stream_lines, First, Rng};
+use crate::Params;
+use itertools::Itertools;
+use std::collections::HashSet;
+use std::io::BufRead;
+
+pub fn part_one(input: &str) -> Option<usize> {
+    compartment_priorities(input.lines().map(Some))
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    badge_priorities(input.lines().map(Some))
+}
+
+pub fn part_one_stream(input: impl BufRead) -> Option<usize> {
+    compartment_priorities(stream_lines(input))
+}
+
+pub fn part_two_stream(input: impl BufRead) -> Option<usize> {
+    badge_priorities(stream_lines(input))
+}
+
+/// Sum of the priorities of the item type in both compartments of each rucksack.
+fn compartment_priorities<L: AsRef<str>>(lines: impl Iterator<Item = Option<L>>) -> Option<usize> {
+    lines
+        .map(|rucksack| {
+            let rucksack = rucksack?;
+            let rucksack = rucksack.as_ref();
+            // `None` if the middle falls inside a multi-byte character.
+            let duplicate_item = find_duplicate_items(vec![
+                rucksack.get(0..rucksack.len() / 2)?,
+                rucksack.get(rucksack.len() / 2..rucksack.len())?,
+            ])?;
+            priority(duplicate_item.first())
+        })
+        .sum()
+}
+
+/// Sum of the priorities of the badge shared by each group of three rucksacks.
+fn badge_priorities<L: AsRef<str>>(lines: impl Iterator<Item = Option<L>>) -> Option<usize> {
+    lines
+        .chunks(3)
+        .into_iter()
+        .map(|group| {
+            let group = group.collect::<Option<Vec<_>>>()?;
+            let duplicate_item =
+                find_duplicate_items(group.iter().map(|rucksack| rucksack.as_ref()).collect())?;
+            priority(duplicate_item.first())
+        })
+        .sum()
+}
+
+pub fn find_duplicate_items(input: Vec<&str>) -> Option<HashSet<char>> {
+    input
+        .into_iter()
+        .map(|rucksack| -> HashSet<char> { HashSet::from_iter(rucksack.chars()) })
+        .reduce(|duplicates, seen_types| duplicates.intersection(&seen_types).copied().collect())
+}
+
+pub fn priority(input: Option<&char>) -> Option<usize> {
+    if let Some(input) = input {
+        if *input >= 'A' && *input <= 'Z' {
+            return Some(*input as usize - 'A' as usize + 27);
+        } else if *input >= 'a' && *input <= 'z' {
+            return Some(*input as usize - 'a' as usize + 1);
+        }
+    }
+    None
+}
+
+/// `size` groups of three rucksacks. Each group shares exactly one badge, and each rucksack
+/// has exactly one item type in both compartments.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    let mut items = ('a'..='z').chain('A'..='Z').collect_vec();
+    (0..size.max(1))
+        .flat_map(|_| {
+            rng.shuffle(&mut items);
+            let (badge, rest) = items.split_first().unwrap();
+            // disjoint pools, so the badge is the only type all three can share.
+            rest.chunks(rest.len() / 3)
+                .map(|pool| rucksack(*badge, pool, rng))
+                .collect_vec()
+        })
+        .join("\n")
+}
+
+fn rucksack(badge: char, pool: &[char], rng: &mut Rng) -> String {
+    let mut pool = pool.to_vec();
+    rng.shuffle(&mut pool);
+    let shared = match rng.chance(1, 4) {
+        true => badge,
+        false => pool.pop().unwrap(),
+    };
+    let (left, right) = pool.split_at(pool.len() / 2);
+    let length = rng.between(2, 16) as usize;
+    let badge_left = rng.chance(1, 2);
+    [(left, badge_left), (right, !badge_left)]
+        .map(|(items, with_badge)| {
+            let mut compartment = vec![shared];
+            if with_badge && shared != badge {
+                compartment.push(badge);
+            }
+            while compartment.len() < length {
+                compartment.push(*rng.choose(items));
+            }
+            rng.shuffle(&mut compartment);
+            compartment.into_iter().collect::<String>()
+        })
+        .concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 3);
+        assert_eq!(part_one(&input), Some(157));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 3);
+        assert_eq!(part_two(&input), Some(70));
+    }
+}
@@ -0,0 +1,86 @@
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+
+use crate::helpers::{stream_lines, FullyContains, Overlaps, Rng};
+use crate::Params;
+use itertools::Itertools;
+
+pub fn part_one(input: &str) -> Option<usize> {
+    count_pairs(input.lines().map(Some), |range1, range2| {
+        range1.fully_contains(range2)
+    })
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    count_pairs(input.lines().map(Some), |range1, range2| {
+        range1.overlaps(range2)
+    })
+}
+
+pub fn part_one_stream(input: impl BufRead) -> Option<usize> {
+    count_pairs(stream_lines(input), |range1, range2| {
+        range1.fully_contains(range2)
+    })
+}
+
+pub fn part_two_stream(input: impl BufRead) -> Option<usize> {
+    count_pairs(stream_lines(input), |range1, range2| {
+        range1.overlaps(range2)
+    })
+}
+
+/// How many pairs of assignments satisfy `matches`.
+fn count_pairs<L: AsRef<str>>(
+    lines: impl Iterator<Item = Option<L>>,
+    matches: impl Fn(&RangeInclusive<usize>, &RangeInclusive<usize>) -> bool,
+) -> Option<usize> {
+    lines
+        .map(|line| {
+            let line = line?;
+            let (range1, range2) = to_ranges(&line.as_ref().split(&[',', '-'][..]).collect_vec())?;
+            Some(matches(&range1, &range2) as usize)
+        })
+        .sum()
+}
+
+/// The two ranges of a pair, or `None` unless there are exactly four bounds, in order.
+pub fn to_ranges(ranges: &[&str]) -> Option<(RangeInclusive<usize>, RangeInclusive<usize>)> {
+    let ranges: Vec<usize> = ranges
+        .iter()
+        .map(|range| range.parse().ok())
+        .collect::<Option<_>>()?;
+
+    match ranges[..] {
+        [a, b, c, d] if a <= b && c <= d => {
+            Some((RangeInclusive::new(a, b), RangeInclusive::new(c, d)))
+        }
+        _ => None,
+    }
+}
+
+/// `size` pairs of random section assignments.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let [a, b, c, d] = [(); 4].map(|_| rng.between(1, 99));
+            format!("{}-{},{}-{}", a.min(b), a.max(b), c.min(d), c.max(d))
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 4);
+        assert_eq!(part_one(&input), Some(2));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 4);
+        assert_eq!(part_two(&input), Some(4));
+    }
+}
@@ -0,0 +1,404 @@
+use std::fmt;
+
+use crate::helpers::Rng;
+use crate::{Param, Params};
+use itertools::Itertools;
+
+pub type Stack = Vec<char>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    MissingMoves,
+    Drawing(usize),
+    DuplicateLabel(String),
+    Syntax(usize),
+    NoSuchStack {
+        line: usize,
+        label: String,
+    },
+    NotEnoughCrates {
+        line: usize,
+        label: String,
+        wanted: usize,
+        held: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingMoves => write!(f, "no blank line separates the drawing from the moves"),
+            Error::Drawing(line) => {
+                write!(
+                    f,
+                    "line {}: the drawing has something other than crates under stack labels",
+                    line
+                )
+            }
+            Error::DuplicateLabel(label) => {
+                write!(f, "stack `{}` is labelled more than once", label)
+            }
+            Error::Syntax(line) => write!(f, "line {}: expected `move N from A to B`", line),
+            Error::NoSuchStack { line, label } => {
+                write!(f, "line {}: there is no stack `{}`", line, label)
+            }
+            Error::NotEnoughCrates {
+                line,
+                label,
+                wanted,
+                held,
+            } => write!(
+                f,
+                "line {}: cannot move {} crates from stack `{}`, which holds {}",
+                line, wanted, label, held
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A move between two stacks, by index, with the input line it was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub line: usize,
+    pub quantity: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// How a crane puts down the crates it lifts off the top of a stack.
+pub trait Crane {
+    /// Reorders `lifted`, bottom crate first, into the order they end up in on the new stack.
+    fn restack(&self, lifted: &mut [char]);
+}
+
+/// Moves one crate at a time, so the lifted crates end up reversed.
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn restack(&self, lifted: &mut [char]) {
+        lifted.reverse();
+    }
+}
+
+/// Moves all the lifted crates at once, keeping their order.
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn restack(&self, _lifted: &mut [char]) {}
+}
+
+/// Moves up to `capacity` crates at a time, each batch keeping its order.
+pub struct CrateMover {
+    pub capacity: usize,
+}
+
+impl Crane for CrateMover {
+    fn restack(&self, lifted: &mut [char]) {
+        let batches = lifted
+            .rchunks(self.capacity.max(1))
+            .flatten()
+            .copied()
+            .collect_vec();
+        lifted.copy_from_slice(&batches);
+    }
+}
+
+/// The stacks of crates, bottom crate first, and the labels they are drawn above.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cargo {
+    pub labels: Vec<String>,
+    pub stacks: Vec<Stack>,
+}
+
+impl Cargo {
+    /// Reads a drawing whose last line labels the stacks. Each crate belongs to the label its
+    /// letter is drawn above, so labels may be any width and lines may be cut short.
+    pub fn parse(drawing: &[&str]) -> Result<Cargo, Error> {
+        let (label_line, crate_lines) = drawing.split_last().ok_or(Error::Drawing(1))?;
+        let label_line = label_line.chars().collect_vec();
+        let mut spans = vec![];
+        let mut labels: Vec<String> = vec![];
+        for (is_label, group) in &label_line
+            .iter()
+            .enumerate()
+            .group_by(|(_, character)| !character.is_whitespace())
+        {
+            if is_label {
+                let group = group.collect_vec();
+                let label = group.iter().map(|(_, character)| *character).collect();
+                if labels.contains(&label) {
+                    return Err(Error::DuplicateLabel(label));
+                }
+                spans.push(group[0].0..group[0].0 + group.len());
+                labels.push(label);
+            }
+        }
+        if labels.is_empty() {
+            return Err(Error::Drawing(drawing.len()));
+        }
+
+        let mut stacks = vec![Stack::new(); labels.len()];
+        for (level, (idx, line)) in crate_lines.iter().enumerate().rev().enumerate() {
+            let line = line.chars().collect_vec();
+            let mut position = 0;
+            while position < line.len() {
+                match line[position..] {
+                    [' ', ..] => position += 1,
+                    ['[', name, ']', ..] if name != ' ' => {
+                        // crates rest on the one below them, never above a gap.
+                        let stack = spans
+                            .iter()
+                            .position(|span| span.contains(&(position + 1)))
+                            .filter(|stack| stacks[*stack].len() == level)
+                            .ok_or(Error::Drawing(idx + 1))?;
+                        stacks[stack].push(name);
+                        position += 3;
+                    }
+                    _ => return Err(Error::Drawing(idx + 1)),
+                }
+            }
+        }
+        Ok(Cargo { labels, stacks })
+    }
+
+    /// Index of the stack drawn above `label`.
+    pub fn stack(&self, label: &str) -> Option<usize> {
+        self.labels.iter().position(|other| other == label)
+    }
+
+    /// Carries out a move read for this cargo, failing if the stack is too short for it.
+    pub fn apply(&mut self, m: &Move, crane: &dyn Crane) -> Result<(), Error> {
+        let held = self.stacks[m.from].len();
+        let remaining = held
+            .checked_sub(m.quantity)
+            .ok_or_else(|| Error::NotEnoughCrates {
+                line: m.line,
+                label: self.labels[m.from].clone(),
+                wanted: m.quantity,
+                held,
+            })?;
+        let mut lifted = self.stacks[m.from].split_off(remaining);
+        crane.restack(&mut lifted);
+        self.stacks[m.to].append(&mut lifted);
+        Ok(())
+    }
+
+    /// The crate on top of each stack, or `None` if a stack is empty.
+    pub fn tops(&self) -> Option<String> {
+        self.stacks.iter().map(|stack| stack.last()).collect()
+    }
+
+    /// The drawing `parse` reads: every column is its label padded by a space on each side,
+    /// and crates sit over the middle of their label.
+    pub fn render(&self) -> String {
+        let widths = self
+            .labels
+            .iter()
+            .map(|label| label.chars().count())
+            .collect_vec();
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let mut lines = (0..height)
+            .rev()
+            .map(|level| {
+                self.stacks
+                    .iter()
+                    .zip(&widths)
+                    .map(|(stack, width)| {
+                        let offset = (width - 1) / 2;
+                        match stack.get(level) {
+                            Some(name) => format!(
+                                "{}[{}]{}",
+                                " ".repeat(offset),
+                                name,
+                                " ".repeat(width - 1 - offset)
+                            ),
+                            None => " ".repeat(width + 2),
+                        }
+                    })
+                    .join(" ")
+            })
+            .collect_vec();
+        lines.push(
+            self.labels
+                .iter()
+                .map(|label| format!(" {} ", label))
+                .join(" "),
+        );
+        lines.join("\n")
+    }
+}
+
+/// The starting cargo and the moves, which are validated against its labels.
+pub fn parse_input(input: &str) -> Result<(Cargo, Vec<Move>), Error> {
+    let lines = input.lines().collect_vec();
+    let blank = lines
+        .iter()
+        .position(|line| line.is_empty())
+        .ok_or(Error::MissingMoves)?;
+    let cargo = Cargo::parse(&lines[..blank])?;
+
+    let moves = lines[blank + 1..]
+        .iter()
+        .zip(blank + 2..)
+        .map(
+            |(text, line)| match text.split_whitespace().collect_vec()[..] {
+                ["move", quantity, "from", from, "to", to] => {
+                    let stack = |label: &str| {
+                        cargo.stack(label).ok_or_else(|| Error::NoSuchStack {
+                            line,
+                            label: label.to_string(),
+                        })
+                    };
+                    Ok(Move {
+                        line,
+                        quantity: quantity.parse().map_err(|_| Error::Syntax(line))?,
+                        from: stack(from)?,
+                        to: stack(to)?,
+                    })
+                }
+                _ => Err(Error::Syntax(line)),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((cargo, moves))
+}
+
+/// The cargo after `crane` has carried out every move.
+pub fn operate(input: &str, crane: &dyn Crane) -> Result<Cargo, Error> {
+    let (mut cargo, moves) = parse_input(input)?;
+    for m in &moves {
+        cargo.apply(m, crane)?;
+    }
+    Ok(cargo)
+}
+
+pub fn part_one(input: &str) -> Option<String> {
+    operate(input, &CrateMover9000).ok()?.tops()
+}
+
+pub fn part_two(input: &str) -> Option<String> {
+    operate(input, &CrateMover9001).ok()?.tops()
+}
+
+/// How many stacks `generate` lays out.
+pub const PARAMS: &[Param] = &[Param::new::<usize>("stacks", "9")];
+
+/// `stacks` stacks (nine by default) of two to eight crates, then `size` moves that never
+/// empty a stack.
+pub fn generate(size: usize, rng: &mut Rng, params: &Params) -> String {
+    let stacks = (0..params.get::<usize>(PARAMS, "stacks").max(2))
+        .map(|_| {
+            (0..rng.between(2, 8))
+                .map(|_| (b'A' + rng.below(26) as u8) as char)
+                .collect_vec()
+        })
+        .collect_vec();
+    let cargo = Cargo {
+        labels: (1..=stacks.len()).map(|idx| idx.to_string()).collect(),
+        stacks,
+    };
+
+    let mut heights = cargo.stacks.iter().map(Vec::len).collect_vec();
+    let moves = (0..size)
+        .map(|_| {
+            // there are more crates than stacks, so some stack can always spare one.
+            let sources = (0..heights.len())
+                .filter(|idx| heights[*idx] > 1)
+                .collect_vec();
+            let from = *rng.choose(&sources);
+            let to = (from + 1 + rng.below(heights.len() - 1)) % heights.len();
+            let quantity = rng.between(1, heights[from] as isize - 1) as usize;
+            heights[from] -= quantity;
+            heights[to] += quantity;
+            format!("move {} from {} to {}", quantity, from + 1, to + 1)
+        })
+        .collect_vec();
+
+    format!("{}\n\n{}", cargo.render(), moves.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 5);
+        assert_eq!(part_one(&input), Some("CMZ".to_string()));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 5);
+        assert_eq!(part_two(&input), Some("MCD".to_string()));
+    }
+
+    #[test]
+    fn test_cranes() {
+        let input = crate::read_file("examples", 5);
+        let tops = |crane: &dyn Crane| operate(&input, crane).unwrap().tops();
+        assert_eq!(tops(&CrateMover { capacity: 1 }), tops(&CrateMover9000));
+        assert_eq!(tops(&CrateMover { capacity: 3 }), tops(&CrateMover9001));
+
+        let mut lifted = ['a', 'b', 'c', 'd', 'e'];
+        CrateMover { capacity: 2 }.restack(&mut lifted);
+        assert_eq!(lifted, ['d', 'e', 'b', 'c', 'a']);
+    }
+
+    #[test]
+    fn test_render() {
+        let input = crate::read_file("examples", 5);
+        let (mut cargo, moves) = parse_input(&input).unwrap();
+        assert_eq!(cargo.render(), input.split("\n\n").next().unwrap());
+
+        cargo.apply(&moves[0], &CrateMover9000).unwrap();
+        assert_eq!(
+            cargo.render(),
+            "[D]        \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 "
+        );
+
+        let input = "        [Q]\n[X]     [R]\n 9  10  11 \n\nmove 2 from 11 to 9";
+        let cargo = operate(input, &CrateMover9001).unwrap();
+        assert_eq!(cargo.stacks, [vec!['X', 'R', 'Q'], vec![], vec![]]);
+        assert_eq!(
+            cargo.render(),
+            "[Q]          \n[R]          \n[X]          \n 9   10   11 "
+        );
+        assert_eq!(
+            parse_input(&format!("{}\n\n", cargo.render())).unwrap().0,
+            cargo
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = crate::read_file("examples", 5);
+        let drawing = input.split("\n\n").next().unwrap();
+        let with_moves =
+            |moves: &str| operate(&format!("{}\n\n{}", drawing, moves), &CrateMover9000);
+        assert_eq!(
+            with_moves("move 1 from 2 to 1\nmove 4 from 1 to 3"),
+            Err(Error::NotEnoughCrates {
+                line: 7,
+                label: "1".to_string(),
+                wanted: 4,
+                held: 3,
+            })
+        );
+        assert_eq!(
+            with_moves("move 1 from 4 to 1"),
+            Err(Error::NoSuchStack {
+                line: 6,
+                label: "4".to_string(),
+            })
+        );
+        assert_eq!(with_moves("move one from 1 to 2"), Err(Error::Syntax(6)));
+        assert_eq!(operate(drawing, &CrateMover9000), Err(Error::MissingMoves));
+        assert_eq!(
+            operate("[A]\n    [B]\n 1   2 \n\n", &CrateMover9000),
+            Err(Error::Drawing(1))
+        );
+    }
+}
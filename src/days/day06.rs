@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read};
+
+use crate::helpers::Rng;
+use crate::Params;
+use itertools::Itertools;
+
+pub fn part_one(input: &str) -> Option<usize> {
+    unique_sequence(input, &4)
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    unique_sequence(input, &14)
+}
+
+pub fn part_one_stream(input: impl BufRead) -> Option<usize> {
+    first(Markers::buffered(input, &[4])).ok()?[0]
+}
+
+pub fn part_two_stream(input: impl BufRead) -> Option<usize> {
+    first(Markers::buffered(input, &[14])).ok()?[0]
+}
+
+pub fn unique_sequence(input: &str, length: &usize) -> Option<usize> {
+    first(Markers::buffered(input.as_bytes(), &[*length])).ok()?[0]
+}
+
+/// Tracks how many of the latest characters are all different. Only where each character was
+/// last seen is kept, so every character costs the same whatever the window lengths.
+#[derive(Debug, Clone)]
+pub struct Detector {
+    /// One past the position of each character's latest occurrence, or 0 if it has not
+    /// occurred: ASCII in an array, anything else in a map.
+    ascii: [usize; 128],
+    others: HashMap<char, usize>,
+    start: usize,
+    position: usize,
+}
+
+impl Default for Detector {
+    fn default() -> Self {
+        Detector {
+            ascii: [0; 128],
+            others: HashMap::new(),
+            start: 0,
+            position: 0,
+        }
+    }
+}
+
+impl Detector {
+    /// Reads one more character and returns how many of the latest ones are now all different.
+    pub fn push(&mut self, c: char) -> usize {
+        let last_seen = match c.is_ascii() {
+            true => &mut self.ascii[c as usize],
+            false => self.others.entry(c).or_insert(0),
+        };
+        self.start = self.start.max(*last_seen);
+        self.position += 1;
+        *last_seen = self.position;
+        self.position - self.start
+    }
+
+    /// How many characters have been read.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// The characters of a UTF-8 stream, decoded as they are read.
+pub struct Chars<R> {
+    bytes: io::Bytes<R>,
+}
+
+impl<R: Read> Chars<BufReader<R>> {
+    pub fn new(input: R) -> Chars<BufReader<R>> {
+        Chars::buffered(BufReader::new(input))
+    }
+}
+
+impl<R: BufRead> Chars<R> {
+    /// Reads input that is already buffered as it is.
+    pub fn buffered(input: R) -> Chars<R> {
+        Chars {
+            bytes: input.bytes(),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Chars<R> {
+    type Item = io::Result<char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.bytes.next()? {
+            Ok(byte) => byte,
+            Err(error) => return Some(Err(error)),
+        };
+        // the leading byte tells how many follow; anything else is not the start of a character.
+        let width = match first {
+            0x00..=0x7f => 1,
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => 0,
+        };
+        let mut encoded = [first, 0, 0, 0];
+        for byte in encoded.iter_mut().take(width).skip(1) {
+            match self.bytes.next() {
+                Some(Ok(next)) => *byte = next,
+                Some(Err(error)) => return Some(Err(error)),
+                None => break,
+            }
+        }
+        let decoded = std::str::from_utf8(&encoded[..width]).ok();
+        Some(
+            decoded
+                .and_then(|c| c.chars().next())
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "signal is not UTF-8")),
+        )
+    }
+}
+
+/// Every marker in a signal, as the window length and how many characters are read by its end,
+/// in the order they are read. Windows are checked in the order of `lengths` at each position.
+pub struct Markers<R> {
+    chars: Chars<R>,
+    detector: Detector,
+    lengths: Vec<usize>,
+    next_length: usize,
+    distinct: usize,
+}
+
+impl<R: Read> Markers<BufReader<R>> {
+    pub fn new(input: R, lengths: &[usize]) -> Markers<BufReader<R>> {
+        Markers::buffered(BufReader::new(input), lengths)
+    }
+}
+
+impl<R: BufRead> Markers<R> {
+    /// Reads input that is already buffered as it is.
+    pub fn buffered(input: R, lengths: &[usize]) -> Markers<R> {
+        Markers {
+            chars: Chars::buffered(input),
+            detector: Detector::default(),
+            lengths: lengths.to_vec(),
+            next_length: lengths.len(),
+            distinct: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Markers<R> {
+    type Item = io::Result<(usize, usize)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while let Some(length) = self.lengths.get(self.next_length) {
+                self.next_length += 1;
+                if self.distinct >= *length {
+                    return Some(Ok((*length, self.detector.position())));
+                }
+            }
+            match self.chars.next()? {
+                Ok(c) => self.distinct = self.detector.push(c),
+                Err(error) => return Some(Err(error)),
+            }
+            self.next_length = 0;
+        }
+    }
+}
+
+/// How many characters are read until the first marker of each length, reading only as far as
+/// the last of them.
+pub fn first_markers(input: impl Read, lengths: &[usize]) -> io::Result<Vec<Option<usize>>> {
+    first(Markers::new(input, lengths))
+}
+
+fn first(markers: Markers<impl BufRead>) -> io::Result<Vec<Option<usize>>> {
+    let lengths = markers.lengths.clone();
+    let mut first = vec![None; lengths.len()];
+    for marker in markers {
+        let (length, position) = marker?;
+        for (slot, _) in first
+            .iter_mut()
+            .zip(&lengths)
+            .filter(|(_, other)| **other == length)
+        {
+            slot.get_or_insert(position);
+        }
+        if first.iter().all(Option::is_some) {
+            break;
+        }
+    }
+    Ok(first)
+}
+
+/// `size` characters drawn from three letters, too few for either marker, followed by fourteen
+/// distinct letters and some random noise.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    let mut letters = ('a'..='z').collect_vec();
+    rng.shuffle(&mut letters);
+    let (noise, marker) = (&letters[..3], &letters[3..17]);
+    let prefix = (0..size).map(|_| *rng.choose(noise)).collect_vec();
+    let suffix = (0..size / 4).map(|_| (b'a' + rng.below(26) as u8) as char);
+    prefix
+        .into_iter()
+        .chain(marker.iter().copied())
+        .chain(suffix)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let inputs = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11),
+        ];
+        for input in inputs {
+            assert_eq!(part_one(input.0), Some(input.1));
+        }
+    }
+
+    #[test]
+    fn test_part_two() {
+        let inputs = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 26),
+        ];
+        for input in inputs {
+            assert_eq!(part_two(input.0), Some(input.1));
+        }
+
+        let input = crate::read_file("examples", 6);
+        assert_eq!(part_two(&input), None);
+    }
+
+    #[test]
+    fn test_markers() {
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(
+            first_markers(input.as_bytes(), &[14, 4, 1, 30]).unwrap(),
+            [Some(19), Some(7), Some(1), None]
+        );
+
+        let every = Markers::new("ABCA.AB".as_bytes(), &[3, 2])
+            .map(Result::unwrap)
+            .collect_vec();
+        let every = |length| {
+            every
+                .iter()
+                .filter(|(other, _)| *other == length)
+                .map(|(_, position)| *position)
+                .collect_vec()
+        };
+        assert_eq!(every(3), [3, 4, 5, 7]);
+        assert_eq!(every(2), [2, 3, 4, 5, 6, 7]);
+
+        // any character is a letter of the signal, and positions count characters.
+        let input = "ÅÅ01ÅÅ";
+        assert_eq!(first_markers(input.as_bytes(), &[3]).unwrap(), [Some(4)]);
+        let input = "αβγαδ€😀";
+        assert_eq!(
+            first_markers(input.as_bytes(), &[4, 5]).unwrap(),
+            [Some(5), Some(6)]
+        );
+        let error = first_markers(&b"ab\xffcd"[..], &[4]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(first_markers(&"ab€".as_bytes()[..4], &[4]).is_err());
+
+        // a reader without a buffer of its own.
+        let input = io::repeat(b'a').take(3).chain("bcd".as_bytes());
+        assert_eq!(first_markers(input, &[4]).unwrap(), [Some(6)]);
+    }
+}
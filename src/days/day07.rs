@@ -0,0 +1,995 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Range;
+
+use crate::helpers::Rng;
+use crate::{Param, Params};
+use itertools::Itertools;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Syntax(usize),
+    NoSuchDirectory { line: usize, name: String },
+    Conflict { line: usize, path: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Syntax(line) => {
+                write!(
+                    f,
+                    "line {}: expected `$ cd <dir>`, `$ ls` or a listing",
+                    line
+                )
+            }
+            Error::NoSuchDirectory { line, name } => {
+                write!(f, "line {}: there is no directory `{}` here", line, name)
+            }
+            Error::Conflict { line, path } => {
+                write!(
+                    f,
+                    "line {}: the listing of `{}` contradicts itself or an earlier one",
+                    line, path
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// An entry of a directory, in the order `ls` printed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entry {
+    Directory(usize),
+    File { name: String, size: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Directory {
+    pub idx: usize,
+    pub name: String,
+    pub parent: Option<usize>,
+    pub entries: Vec<Entry>,
+    /// Whether `ls` was run here, so `entries` is complete.
+    pub listed: bool,
+}
+
+impl Directory {
+    pub fn new(idx: usize, name: &str, parent: Option<usize>) -> Directory {
+        Directory {
+            idx,
+            name: name.to_string(),
+            parent,
+            entries: vec![],
+            listed: false,
+        }
+    }
+
+    pub fn children(&self) -> impl Iterator<Item = usize> + '_ {
+        self.entries.iter().filter_map(|entry| match entry {
+            Entry::Directory(child) => Some(*child),
+            Entry::File { .. } => None,
+        })
+    }
+
+    /// Total size of the files directly inside, not in subdirectories.
+    pub fn size(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|entry| match entry {
+                Entry::Directory(_) => 0,
+                Entry::File { size, .. } => *size,
+            })
+            .sum()
+    }
+}
+
+/// Directories by index; a directory is always created after its parent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filesystem {
+    pub directories: Vec<Directory>,
+}
+
+pub const ROOT_DIRECTORY: usize = 0;
+
+impl Filesystem {
+    pub fn new() -> Filesystem {
+        Filesystem {
+            directories: vec![Directory::new(ROOT_DIRECTORY, "/", None)],
+        }
+    }
+
+    pub fn name<'a>(&'a self, entry: &'a Entry) -> &'a str {
+        match entry {
+            Entry::Directory(directory) => &self.directories[*directory].name,
+            Entry::File { name, .. } => name,
+        }
+    }
+
+    /// The subdirectory of `directory` called `name`.
+    pub fn child(&self, directory: usize, name: &str) -> Option<usize> {
+        self.directories[directory]
+            .children()
+            .find(|child| self.directories[*child].name == name)
+    }
+
+    /// Absolute path of `directory`, like `/a/e`.
+    pub fn path(&self, directory: usize) -> String {
+        let mut names = vec![];
+        let mut current = directory;
+        while let Some(parent) = self.directories[current].parent {
+            names.push(self.directories[current].name.as_str());
+            current = parent;
+        }
+        format!("/{}", names.iter().rev().join("/"))
+    }
+
+    /// The directory at an absolute path, like `/a/e` or `/a/e/`.
+    pub fn lookup(&self, path: &str) -> Option<usize> {
+        path.strip_prefix('/')?
+            .split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(ROOT_DIRECTORY, |directory, name| {
+                self.child(directory, name)
+            })
+    }
+
+    /// The subdirectory `cd name` moves to. Before `ls` has been run, any name is taken to exist.
+    fn enter(&mut self, directory: usize, name: &str, line: usize) -> Result<usize, Error> {
+        match self.child(directory, name) {
+            Some(child) => Ok(child),
+            None if !self.directories[directory].listed => Ok(self.create(directory, name)),
+            None => Err(Error::NoSuchDirectory {
+                line,
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    fn create(&mut self, parent: usize, name: &str) -> usize {
+        let idx = self.directories.len();
+        self.directories
+            .push(Directory::new(idx, name, Some(parent)));
+        self.directories[parent].entries.push(Entry::Directory(idx));
+        idx
+    }
+
+    /// Records what `ls` printed in `directory`, as names with a size for files. Listing the
+    /// same directory again must give the same entries.
+    fn list(
+        &mut self,
+        directory: usize,
+        listing: &[(&str, Option<usize>)],
+        line: usize,
+    ) -> Result<(), Error> {
+        let conflict = || Error::Conflict {
+            line,
+            path: self.path(directory),
+        };
+        if listing
+            .iter()
+            .map(|(name, _)| name)
+            .duplicates()
+            .next()
+            .is_some()
+        {
+            return Err(conflict());
+        }
+        let known = self.directories[directory]
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                Entry::Directory(_) => (self.name(entry), None),
+                Entry::File { name, size } => (name.as_str(), Some(*size)),
+            })
+            .collect_vec();
+        if self.directories[directory].listed {
+            return match known.iter().sorted().eq(listing.iter().sorted()) {
+                true => Ok(()),
+                false => Err(conflict()),
+            };
+        }
+        // directories entered before the first `ls` must show up in it.
+        if !known.iter().all(|entry| listing.contains(entry)) {
+            return Err(conflict());
+        }
+
+        let children = self.directories[directory]
+            .children()
+            .map(|child| (self.directories[child].name.clone(), child))
+            .collect_vec();
+        self.directories[directory].entries.clear();
+        for (name, size) in listing {
+            let entry = match size {
+                Some(size) => Entry::File {
+                    name: name.to_string(),
+                    size: *size,
+                },
+                None => match children.iter().find(|(other, _)| other == name) {
+                    Some((_, child)) => Entry::Directory(*child),
+                    None => {
+                        self.create(directory, name);
+                        continue;
+                    }
+                },
+            };
+            self.directories[directory].entries.push(entry);
+        }
+        self.directories[directory].listed = true;
+        Ok(())
+    }
+
+    /// Total size of every directory, children first, as `du -h` prints it.
+    pub fn du(&self) -> String {
+        let sizes = calculate_directory_total_sizes(self);
+        let mut order = vec![];
+        let mut stack = vec![ROOT_DIRECTORY];
+        while let Some(directory) = stack.pop() {
+            order.push(directory);
+            stack.extend(self.directories[directory].children());
+        }
+        order
+            .iter()
+            .rev()
+            .map(|directory| {
+                format!(
+                    "{}\t{}",
+                    human_size(sizes[*directory]),
+                    self.path(*directory)
+                )
+            })
+            .join("\n")
+    }
+
+    /// Absolute path of what `deletion` removes.
+    pub fn deletion_path(&self, deletion: &Deletion) -> String {
+        match deletion {
+            Deletion::Directory(directory) => self.path(*directory),
+            Deletion::File(directory, entry) => {
+                let entry = &self.directories[*directory].entries[*entry];
+                match *directory {
+                    ROOT_DIRECTORY => format!("/{}", self.name(entry)),
+                    _ => format!("{}/{}", self.path(*directory), self.name(entry)),
+                }
+            }
+        }
+    }
+
+    /// Every directory and file, nested under their parents in listing order, as drawn in the
+    /// puzzle.
+    pub fn tree(&self) -> String {
+        let root = Entry::Directory(ROOT_DIRECTORY);
+        let mut lines = vec![];
+        let mut stack = vec![(0, &root)];
+        while let Some((depth, entry)) = stack.pop() {
+            let indent = "  ".repeat(depth);
+            match entry {
+                Entry::Directory(directory) => {
+                    lines.push(format!("{}- {} (dir)", indent, self.name(entry)));
+                    let entries = &self.directories[*directory].entries;
+                    stack.extend(entries.iter().rev().map(|entry| (depth + 1, entry)));
+                }
+                Entry::File { name, size } => {
+                    lines.push(format!("{}- {} (file, size={})", indent, name, size))
+                }
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+impl Default for Filesystem {
+    fn default() -> Self {
+        Filesystem::new()
+    }
+}
+
+/// A size in bytes as `du -h` prints it: in the largest unit of 1024 that keeps it at least 1,
+/// rounded up, with one decimal below 10.
+fn human_size(size: usize) -> String {
+    const UNITS: [&str; 6] = ["K", "M", "G", "T", "P", "E"];
+    if size < 1024 {
+        return size.to_string();
+    }
+    let mut scaled = size as f64 / 1024.0;
+    let mut unit = 0;
+    while scaled >= 1024.0 && unit + 1 < UNITS.len() {
+        scaled /= 1024.0;
+        unit += 1;
+    }
+    let tenths = (scaled * 10.0).ceil() / 10.0;
+    match tenths < 10.0 {
+        true => format!("{:.1}{}", tenths, UNITS[unit]),
+        false => format!("{}{}", scaled.ceil(), UNITS[unit]),
+    }
+}
+
+/// Sizes in bytes: the largest directory part one counts, the disk and the space the update
+/// needs on it.
+pub const PARAMS: &[Param] = &[
+    Param::new::<usize>("max_size", "100000"),
+    Param::new::<usize>("disk_size", "70000000"),
+    Param::new::<usize>("required_space", "30000000"),
+];
+
+pub fn part_one(input: &str, params: &Params) -> Option<usize> {
+    let max_size: usize = params.get(PARAMS, "max_size");
+    let filesystem = create_filesystem_from_input(input).ok()?;
+    Some(
+        calculate_directory_total_sizes(&filesystem)
+            .into_iter()
+            .filter(|total_size| *total_size <= max_size)
+            .sum(),
+    )
+}
+
+pub fn part_two(input: &str, params: &Params) -> Option<usize> {
+    let filesystem = create_filesystem_from_input(input).ok()?;
+    let total_sizes = calculate_directory_total_sizes(&filesystem);
+    let need_size = need_size(&total_sizes, params)?;
+
+    total_sizes
+        .into_iter()
+        .filter(|directory_total_size| *directory_total_size >= need_size)
+        .min()
+}
+
+/// How many bytes must be freed to have `required_space` left on a disk of `disk_size`.
+pub fn need_size(total_sizes: &[usize], params: &Params) -> Option<usize> {
+    let disk_size: usize = params.get(PARAMS, "disk_size");
+    let required_space: usize = params.get(PARAMS, "required_space");
+    Some(total_sizes[ROOT_DIRECTORY].saturating_sub(disk_size.checked_sub(required_space)?))
+}
+
+/// The directory tree the session walks. Revisiting a directory, with `cd` or `ls`, finds what
+/// is already known about it.
+pub fn create_filesystem_from_input(input: &str) -> Result<Filesystem, Error> {
+    let mut filesystem = Filesystem::new();
+    let mut current_directory = ROOT_DIRECTORY;
+
+    let mut lines = input
+        .lines()
+        .map(str::trim)
+        .zip(1..)
+        .filter(|(text, _)| !text.is_empty())
+        .peekable();
+    while let Some((text, line)) = lines.next() {
+        let command = text.strip_prefix('$').ok_or(Error::Syntax(line))?;
+        match command.split_whitespace().collect_vec()[..] {
+            ["ls"] => {
+                let mut listing = vec![];
+                while let Some((text, line)) = lines.next_if(|(text, _)| !text.starts_with('$')) {
+                    let (kind, name) = text
+                        .split_once(char::is_whitespace)
+                        .ok_or(Error::Syntax(line))?;
+                    let size = match kind {
+                        "dir" => None,
+                        size => Some(size.parse().map_err(|_| Error::Syntax(line))?),
+                    };
+                    listing.push((name.trim(), size));
+                }
+                filesystem.list(current_directory, &listing, line)?;
+            }
+            // as in a shell, `cd ..` at the root stays there.
+            ["cd", ".."] => {
+                current_directory = filesystem.directories[current_directory]
+                    .parent
+                    .unwrap_or(ROOT_DIRECTORY)
+            }
+            ["cd", "/"] => current_directory = ROOT_DIRECTORY,
+            ["cd", name] => current_directory = filesystem.enter(current_directory, name, line)?,
+            _ => return Err(Error::Syntax(line)),
+        }
+    }
+    Ok(filesystem)
+}
+
+/// Total size of every directory, by index. A directory is created after its parent, so going
+/// through them backwards adds each one into its parent once, after all of its children.
+pub fn calculate_directory_total_sizes(filesystem: &Filesystem) -> Vec<usize> {
+    let mut total_sizes = filesystem
+        .directories
+        .iter()
+        .map(Directory::size)
+        .collect_vec();
+    for directory in filesystem.directories.iter().rev() {
+        if let Some(parent) = directory.parent {
+            total_sizes[parent] += total_sizes[directory.idx];
+        }
+    }
+    total_sizes
+}
+
+/// Something a cleanup deletes: a directory, or a file by its directory and entry index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Deletion {
+    Directory(usize),
+    File(usize, usize),
+}
+
+/// What a cleanup plan keeps as small as possible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    Bytes,
+    /// Fewest deletions, then fewest bytes among those.
+    Deletions,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub deletions: Vec<Deletion>,
+    pub freed: usize,
+}
+
+/// The best set of files and directories, none inside another, whose deletion frees at least
+/// `target` bytes, or `None` if the whole disk does not hold that much. The root stays. With
+/// dozens of files and no way to free close to `target`, the fewest bytes may only be
+/// approached: the search for them is cut short rather than left to grow exponentially.
+pub fn plan_cleanup(filesystem: &Filesystem, target: usize, objective: Objective) -> Option<Plan> {
+    let total_sizes = calculate_directory_total_sizes(filesystem);
+    if total_sizes[ROOT_DIRECTORY] < target {
+        return None;
+    }
+    let mut plan = match objective {
+        Objective::Bytes => fewest_bytes(filesystem, &total_sizes, target),
+        Objective::Deletions => fewest_deletions(filesystem, &total_sizes, target),
+    };
+    plan.deletions.sort();
+    Some(plan)
+}
+
+/// Every file, as its directory, entry index and size.
+fn files(filesystem: &Filesystem) -> Vec<(usize, usize, usize)> {
+    filesystem
+        .directories
+        .iter()
+        .flat_map(|directory| {
+            directory
+                .entries
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, entry)| match entry {
+                    Entry::File { size, .. } => Some((directory.idx, idx, *size)),
+                    Entry::Directory(_) => None,
+                })
+        })
+        .collect()
+}
+
+/// Deleting a directory frees exactly what deleting all the files under it would, so the
+/// fewest bytes come from a subset sum over the files alone. Directories whose files all get
+/// deleted are then deleted whole instead.
+fn fewest_bytes(filesystem: &Filesystem, total_sizes: &[usize], target: usize) -> Plan {
+    let files = files(filesystem);
+    let sizes = files.iter().map(|(_, _, size)| *size).collect_vec();
+    let (freed, chosen) = smallest_sum_reaching(&sizes, target);
+    let deleted: HashSet<_> = chosen
+        .into_iter()
+        .map(|idx| Deletion::File(files[idx].0, files[idx].1))
+        .collect();
+
+    let mut whole = vec![false; filesystem.directories.len()];
+    for directory in filesystem.directories.iter().rev() {
+        whole[directory.idx] =
+            directory
+                .entries
+                .iter()
+                .enumerate()
+                .all(|(idx, entry)| match entry {
+                    Entry::Directory(child) => whole[*child],
+                    Entry::File { .. } => deleted.contains(&Deletion::File(directory.idx, idx)),
+                });
+    }
+    let mut deletions = vec![];
+    let mut stack = vec![ROOT_DIRECTORY];
+    while let Some(directory) = stack.pop() {
+        for (idx, entry) in filesystem.directories[directory].entries.iter().enumerate() {
+            match entry {
+                Entry::Directory(child) if whole[*child] && total_sizes[*child] > 0 => {
+                    deletions.push(Deletion::Directory(*child))
+                }
+                Entry::Directory(child) => stack.push(*child),
+                Entry::File { .. } => {
+                    let file = Deletion::File(directory, idx);
+                    if deleted.contains(&file) {
+                        deletions.push(file);
+                    }
+                }
+            }
+        }
+    }
+    Plan { deletions, freed }
+}
+
+/// Search steps `smallest_sum_reaching` takes before settling for the best sum it has found.
+const SUM_SEARCH_STEPS: usize = 1 << 20;
+
+/// Indices of some `sizes` with a sum that is at least `target`, and that sum: the smallest one
+/// if the search finishes within `SUM_SEARCH_STEPS`, as it does unless there are dozens of
+/// sizes with no sum close to `target`.
+///
+/// A branch and bound search over the sizes, largest first and counted in their greatest common
+/// divisor. Its first path takes the largest sizes until they reach `target`, so there is always
+/// a sum to return, and it costs at most `SUM_SEARCH_STEPS` steps of one pass over the sizes,
+/// whatever the sizes themselves.
+fn smallest_sum_reaching(sizes: &[usize], target: usize) -> (usize, Vec<usize>) {
+    let order = (0..sizes.len())
+        .filter(|idx| sizes[*idx] > 0)
+        .sorted_by_key(|idx| Reverse(sizes[*idx]))
+        .collect_vec();
+    let unit = order.iter().fold(0, |unit, idx| gcd(unit, sizes[*idx]));
+    if target == 0 || unit == 0 {
+        return (0, vec![]);
+    }
+    let units = order.iter().map(|idx| sizes[*idx] / unit).collect_vec();
+    let mut after = vec![0; units.len() + 1];
+    for idx in (0..units.len()).rev() {
+        after[idx] = after[idx + 1] + units[idx];
+    }
+
+    let mut search = SumSearch {
+        units: &units,
+        after: &after,
+        target: target.div_ceil(unit),
+        chosen: vec![],
+        best: None,
+        steps: 0,
+    };
+    search.run(0, 0);
+    let (sum, chosen) = search
+        .best
+        .expect("the sizes add up to at least the target");
+    (
+        sum * unit,
+        chosen.into_iter().map(|idx| order[idx]).collect(),
+    )
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Chooses some of `units`, sorted largest first, where `after[idx]` is what those from `idx` on
+/// add up to.
+struct SumSearch<'a> {
+    units: &'a [usize],
+    after: &'a [usize],
+    target: usize,
+    chosen: Vec<usize>,
+    best: Option<(usize, Vec<usize>)>,
+    steps: usize,
+}
+
+impl SumSearch<'_> {
+    fn run(&mut self, start: usize, sum: usize) {
+        self.steps += 1;
+        if sum >= self.target {
+            if self.best.as_ref().is_none_or(|(best, _)| sum < *best) {
+                self.best = Some((sum, self.chosen.clone()));
+            }
+            return;
+        }
+        let mut idx = start;
+        while idx < self.units.len() {
+            let done = self
+                .best
+                .as_ref()
+                .is_some_and(|(best, _)| *best == self.target || self.steps >= SUM_SEARCH_STEPS);
+            if done {
+                return;
+            }
+            if sum + self.after[idx] < self.target {
+                return;
+            }
+            let size = self.units[idx];
+            if self
+                .best
+                .as_ref()
+                .is_none_or(|(best, _)| sum + size < *best)
+            {
+                self.chosen.push(idx);
+                self.run(idx + 1, sum + size);
+                self.chosen.pop();
+            }
+            // leaving out a size leaves out the equal ones after it, or the same sums come back.
+            while self.units.get(idx) == Some(&size) {
+                idx += 1;
+            }
+        }
+    }
+}
+
+/// The most any `k` deletions free is the `k` largest entries of the root, which fixes how many
+/// are needed. A branch and bound search over every file and directory, largest first, then
+/// finds the fewest bytes for that many.
+fn fewest_deletions(filesystem: &Filesystem, total_sizes: &[usize], target: usize) -> Plan {
+    let root = &filesystem.directories[ROOT_DIRECTORY];
+    let mut largest = root
+        .entries
+        .iter()
+        .map(|entry| match entry {
+            Entry::Directory(child) => total_sizes[*child],
+            Entry::File { size, .. } => *size,
+        })
+        .collect_vec();
+    largest.sort_unstable_by_key(|size| Reverse(*size));
+    let count = (0..=largest.len())
+        .find(|count| largest[..*count].iter().sum::<usize>() >= target)
+        .expect("the root holds at least the target");
+
+    // directories in pre-order, so a directory's subtree is a range of positions.
+    let mut ranges = vec![0..0; filesystem.directories.len()];
+    let mut order = vec![];
+    let mut stack = vec![ROOT_DIRECTORY];
+    while let Some(directory) = stack.pop() {
+        ranges[directory].start = order.len();
+        order.push(directory);
+        stack.extend(filesystem.directories[directory].children());
+    }
+    for &directory in order.iter().rev() {
+        ranges[directory].end = filesystem.directories[directory]
+            .children()
+            .map(|child| ranges[child].end)
+            .max()
+            .unwrap_or(ranges[directory].start + 1);
+    }
+
+    let mut candidates = files(filesystem)
+        .into_iter()
+        .map(|(directory, entry, size)| {
+            let position = ranges[directory].start;
+            (Deletion::File(directory, entry), size, position..position)
+        })
+        .chain(order[1..].iter().map(|directory| {
+            let range = ranges[*directory].clone();
+            (
+                Deletion::Directory(*directory),
+                total_sizes[*directory],
+                range,
+            )
+        }))
+        .filter(|(_, size, _)| *size > 0)
+        .collect_vec();
+    candidates.sort_by_key(|candidate| Reverse(candidate.1));
+
+    let mut search = Search {
+        candidates: &candidates,
+        target,
+        chosen: vec![],
+        best: None,
+    };
+    search.run(0, count, 0);
+    let (freed, chosen) = search
+        .best
+        .expect("the largest entries of the root free enough");
+    Plan {
+        deletions: chosen.into_iter().map(|idx| candidates[idx].0).collect(),
+        freed,
+    }
+}
+
+/// Chooses candidates, each with a size and the pre-order range of directories it covers (empty
+/// for a file, at its directory), none of which overlap.
+struct Search<'a> {
+    candidates: &'a [(Deletion, usize, Range<usize>)],
+    target: usize,
+    chosen: Vec<usize>,
+    best: Option<(usize, Vec<usize>)>,
+}
+
+impl Search<'_> {
+    fn run(&mut self, start: usize, left: usize, freed: usize) {
+        if freed >= self.target {
+            if self.best.as_ref().is_none_or(|(best, _)| freed < *best) {
+                self.best = Some((freed, self.chosen.clone()));
+            }
+            return;
+        }
+        for idx in start..self.candidates.len() {
+            if self
+                .best
+                .as_ref()
+                .is_some_and(|(best, _)| *best == self.target)
+            {
+                return;
+            }
+            // candidates only get smaller from here.
+            let most = self.candidates[idx..]
+                .iter()
+                .take(left)
+                .map(|c| c.1)
+                .sum::<usize>();
+            if freed + most < self.target {
+                return;
+            }
+            let (_, size, range) = &self.candidates[idx];
+            let too_much = self
+                .best
+                .as_ref()
+                .is_some_and(|(best, _)| freed + size >= *best);
+            let overlaps = self.chosen.iter().any(|other| {
+                let other = &self.candidates[*other].2;
+                other.contains(&range.start) || range.contains(&other.start)
+            });
+            if too_much || overlaps {
+                continue;
+            }
+            self.chosen.push(idx);
+            self.run(idx + 1, left - 1, freed + size);
+            self.chosen.pop();
+        }
+    }
+}
+
+/// A terminal session exploring a random tree of `size` directories, depth first. The files
+/// are sized so the disk is too full for the update.
+pub fn generate(size: usize, rng: &mut Rng, params: &Params) -> String {
+    let count = size.max(1);
+    let mut children = vec![vec![]; count];
+    for idx in 1..count {
+        children[rng.below(idx)].push(idx);
+    }
+    let mut files = vec![vec![]; count];
+    for directory in files.iter_mut() {
+        for _ in 0..rng.between(0, 4) {
+            let size = rng.between(1000, 300000) as usize;
+            let name = unique_name(rng, directory);
+            directory.push((size, name));
+        }
+    }
+
+    let disk_size: usize = params.get(PARAMS, "disk_size");
+    let required_space: usize = params.get(PARAMS, "required_space");
+    let total: usize = files.iter().flatten().map(|(size, _)| size).sum();
+    if let Some(missing) = (disk_size - required_space + 1).checked_sub(total) {
+        let size = missing + rng.below(100000);
+        let name = unique_name(rng, &files[ROOT_DIRECTORY]);
+        files[ROOT_DIRECTORY].push((size, name));
+    }
+
+    let mut lines = vec!["$ cd /".to_string()];
+    session(ROOT_DIRECTORY, &children, &files, rng, &mut lines);
+    lines.join("\n")
+}
+
+/// A name that none of `entries` has.
+fn unique_name(rng: &mut Rng, entries: &[(usize, String)]) -> String {
+    let mut name = random_name(rng);
+    while entries.iter().any(|(_, other)| *other == name) {
+        name = random_name(rng);
+    }
+    name
+}
+
+fn random_name(rng: &mut Rng) -> String {
+    (0..rng.between(1, 8))
+        .map(|_| (b'a' + rng.below(26) as u8) as char)
+        .collect()
+}
+
+fn session(
+    directory: usize,
+    children: &[Vec<usize>],
+    files: &[Vec<(usize, String)>],
+    rng: &mut Rng,
+    lines: &mut Vec<String>,
+) {
+    // subdirectories are named unlike each other and the files beside them.
+    let mut names: Vec<String> = vec![];
+    for _ in &children[directory] {
+        let mut child = random_name(rng);
+        while names.contains(&child) || files[directory].iter().any(|(_, name)| *name == child) {
+            child = random_name(rng);
+        }
+        names.push(child);
+    }
+
+    lines.push("$ ls".to_string());
+    lines.extend(names.iter().map(|name| format!("dir {}", name)));
+    lines.extend(
+        files[directory]
+            .iter()
+            .map(|(size, name)| format!("{} {}", size, name)),
+    );
+    for (child, name) in children[directory].iter().zip(&names) {
+        lines.push(format!("$ cd {}", name));
+        session(*child, children, files, rng, lines);
+        lines.push("$ cd ..".to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 7);
+        assert_eq!(part_one(&input, &Params::default()), Some(95437));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 7);
+        assert_eq!(part_two(&input, &Params::default()), Some(24933642));
+    }
+
+    #[test]
+    fn test_plan_cleanup() {
+        let input = crate::read_file("examples", 7);
+        let filesystem = create_filesystem_from_input(&input).unwrap();
+        let plan = |target, objective| {
+            let plan = plan_cleanup(&filesystem, target, objective)?;
+            let paths = plan
+                .deletions
+                .iter()
+                .map(|deletion| filesystem.deletion_path(deletion));
+            Some((paths.collect_vec(), plan.freed))
+        };
+
+        assert_eq!(
+            plan(8381165, Objective::Bytes),
+            Some((vec!["/c.dat".to_string()], 8504156))
+        );
+        assert_eq!(
+            plan(8600000, Objective::Bytes),
+            Some((vec!["/d/j".to_string(), "/d/d.ext".to_string()], 9686326))
+        );
+        assert_eq!(
+            plan(8600000, Objective::Deletions),
+            Some((vec!["/b.txt".to_string()], 14848514))
+        );
+        assert_eq!(
+            plan(94853, Objective::Bytes),
+            Some((vec!["/a".to_string()], 94853))
+        );
+        assert_eq!(
+            plan(30000000, Objective::Deletions),
+            Some((vec!["/d".to_string(), "/c.dat".to_string()], 33437798))
+        );
+        assert_eq!(plan(0, Objective::Deletions), Some((vec![], 0)));
+        assert_eq!(plan(48381166, Objective::Bytes), None);
+    }
+
+    #[test]
+    fn test_plan_gigabytes() {
+        // the search does not grow with the sizes, and they share no divisor to shrink them.
+        let input = [
+            "$ cd /",
+            "$ ls",
+            "dir big",
+            "3000000001 b.bin",
+            "1500000000 d.bin",
+            "$ cd big",
+            "$ ls",
+            "4000000000 a.bin",
+            "2500000000 c.bin",
+        ]
+        .join("\n");
+        let filesystem = create_filesystem_from_input(&input).unwrap();
+        let plan = |target| {
+            let plan = plan_cleanup(&filesystem, target, Objective::Bytes)?;
+            let paths = plan
+                .deletions
+                .iter()
+                .map(|deletion| filesystem.deletion_path(deletion));
+            Some((paths.collect_vec(), plan.freed))
+        };
+        assert_eq!(
+            plan(5500000000),
+            Some((
+                vec!["/d.bin".to_string(), "/big/a.bin".to_string()],
+                5500000000
+            ))
+        );
+        assert_eq!(
+            plan(5500000002),
+            Some((vec!["/big".to_string()], 6500000000))
+        );
+        assert_eq!(plan(11000000002), None);
+    }
+
+    #[test]
+    fn test_plan_many_gigabytes() {
+        // too many large, varied files to search every sum: the search is cut short in time.
+        let mut rng = Rng::new(7);
+        for (count, largest) in [(40, 1000000000000), (40, 2000000000), (60, 1000000000000)] {
+            let sizes = (0..count)
+                .map(|_| 1000000000 + rng.below(largest - 1000000000))
+                .collect_vec();
+            let listing = sizes
+                .iter()
+                .enumerate()
+                .map(|(idx, size)| format!("{} f{}.bin", size, idx))
+                .join("\n");
+            let input = format!("$ cd /\n$ ls\n{}", listing);
+            let filesystem = create_filesystem_from_input(&input).unwrap();
+            let target = sizes.iter().sum::<usize>() / 2;
+
+            let timer = Instant::now();
+            let plan = plan_cleanup(&filesystem, target, Objective::Bytes).unwrap();
+            assert!(timer.elapsed() < Duration::from_secs(5), "{} files", count);
+            let deleted = plan.deletions.iter().map(|deletion| match deletion {
+                Deletion::File(_, idx) => sizes[*idx],
+                Deletion::Directory(_) => panic!("the root stays"),
+            });
+            assert_eq!(plan.freed, deleted.sum::<usize>());
+            // not a single file too many.
+            assert!(plan.freed >= target);
+            assert!(plan.freed - target < *sizes.iter().min().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_filesystem() {
+        let input = crate::read_file("examples", 7);
+        let filesystem = create_filesystem_from_input(&input).unwrap();
+        assert_eq!(filesystem.lookup("/a/e/"), Some(3));
+        assert_eq!(filesystem.path(3), "/a/e");
+        assert_eq!(filesystem.lookup("/a/i"), None);
+        assert_eq!(
+            filesystem.tree(),
+            "- / (dir)
+  - a (dir)
+    - e (dir)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir)
+    - j (file, size=4060174)
+    - d.log (file, size=8033020)
+    - d.ext (file, size=5626152)
+    - k (file, size=7214296)"
+        );
+        assert_eq!(filesystem.du(), "584\t/a/e\n93K\t/a\n24M\t/d\n47M\t/");
+
+        // revisiting directories does not count them twice, and `cd ..` stops at the root.
+        let revisited = [
+            &input,
+            "$ cd ..\n$ cd /\n$ ls\ndir d\ndir a\n8504156 c.dat\n14848514 b.txt",
+            "$ cd a\n$ cd e\n$ ls\n584 i",
+        ]
+        .join("\n");
+        assert_eq!(create_filesystem_from_input(&revisited), Ok(filesystem));
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = crate::read_file("examples", 7);
+        let with = |lines: &str| create_filesystem_from_input(&format!("{}\n{}", input, lines));
+        assert_eq!(
+            with("$ cd /\n$ ls\ndir a\n14848514 b.txt"),
+            Err(Error::Conflict {
+                line: 25,
+                path: "/".to_string(),
+            })
+        );
+        assert_eq!(
+            with("$ cd x"),
+            Err(Error::NoSuchDirectory {
+                line: 24,
+                name: "x".to_string(),
+            })
+        );
+        assert_eq!(with("$ rm -rf /"), Err(Error::Syntax(24)));
+        assert_eq!(
+            create_filesystem_from_input("$ cd a\n$ cd /\n$ ls\n12 a"),
+            Err(Error::Conflict {
+                line: 3,
+                path: "/".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use crate::helpers::{render_grid, shade, Rng};
+use crate::{Param, Params};
+use itertools::Itertools;
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let survey = survey(&parse_grid(input)?);
+    Some(
+        survey
+            .visible
+            .iter()
+            .flatten()
+            .filter(|visible| **visible)
+            .count(),
+    )
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let survey = survey(&parse_grid(input)?);
+    survey.scores.into_iter().flatten().max()
+}
+
+/// Whether each tree can be seen from outside the forest, and its scenic score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Survey {
+    pub visible: Vec<Vec<bool>>,
+    pub scores: Vec<Vec<usize>>,
+}
+
+impl Survey {
+    /// `#` for the trees seen from outside, `.` for the hidden ones.
+    pub fn visibility_map(&self) -> String {
+        render_grid(&self.visible, |visible| match visible {
+            true => '#',
+            false => '.',
+        })
+    }
+
+    /// Scenic scores shaded from blank (zero) to `@` (the best), on a log scale so the few
+    /// best trees do not wash out the rest.
+    pub fn scenic_heatmap(&self) -> String {
+        let level = |score: &usize| (score + 1).ilog2() as usize;
+        let max = self.scores.iter().flatten().map(level).max().unwrap_or(0);
+        render_grid(&self.scores, |score| shade(level(score), max))
+    }
+}
+
+/// Looks along every row and column in both directions. The trees that could still block the
+/// view of a later one form a stack of non-increasing heights: a new tree pops those shorter
+/// than itself, then sees up to the tree left on top, or past the edge if there is none.
+pub fn survey<T: Ord>(heights: &[Vec<T>]) -> Survey {
+    let (rows, columns) = (heights.len(), heights.first().map_or(0, Vec::len));
+    let mut visible = vec![vec![false; columns]; rows];
+    let mut scores = vec![vec![1; columns]; rows];
+
+    let lines = (0..rows)
+        .map(|y| (0..columns).map(|x| (y, x)).collect_vec())
+        .chain((0..columns).map(|x| (0..rows).map(|y| (y, x)).collect_vec()));
+    for line in lines {
+        let reversed = line.iter().rev().copied().collect_vec();
+        for trees in [line, reversed] {
+            let mut stack: Vec<usize> = vec![];
+            for (position, &(y, x)) in trees.iter().enumerate() {
+                let height = &heights[y][x];
+                while let Some(&top) = stack.last() {
+                    let (top_y, top_x) = trees[top];
+                    if heights[top_y][top_x] >= *height {
+                        break;
+                    }
+                    stack.pop();
+                }
+                match stack.last() {
+                    Some(blocker) => scores[y][x] *= position - blocker,
+                    None => {
+                        visible[y][x] = true;
+                        scores[y][x] *= position;
+                    }
+                }
+                stack.push(position);
+            }
+        }
+    }
+    Survey { visible, scores }
+}
+
+/// Tree heights, row by row: each a line of digits, or of whitespace-separated integers for
+/// taller trees. `None` unless the rows make a non-empty rectangle.
+pub fn parse_grid(input: &str) -> Option<Vec<Vec<u32>>> {
+    let grid = input
+        .lines()
+        .map(|line| match line.trim().contains(char::is_whitespace) {
+            true => line
+                .split_whitespace()
+                .map(|height| height.parse().ok())
+                .collect::<Option<Vec<_>>>(),
+            false => line.chars().map(|c| c.to_digit(10)).collect(),
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let width = grid.first()?.len();
+    match width > 0 && grid.iter().all(|row| row.len() == width) {
+        true => Some(grid),
+        false => None,
+    }
+}
+
+/// The shape of generated grids: a `width` of 0 is as many trees as rows.
+pub const PARAMS: &[Param] = &[
+    Param::new::<usize>("width", "0"),
+    Param::new::<usize>("max_height", "9"),
+];
+
+/// A grid of random tree heights, `size` rows of `width` (by default `size`) trees. Heights go
+/// up to `max_height`, 9 by default; above that, rows are written as separate integers.
+pub fn generate(size: usize, rng: &mut Rng, params: &Params) -> String {
+    let rows = size.max(1);
+    let columns = match params.get(PARAMS, "width") {
+        0 => rows,
+        width => width,
+    };
+    let max_height: usize = params.get(PARAMS, "max_height");
+    (0..rows)
+        .map(|_| {
+            let heights = (0..columns).map(|_| rng.below(max_height + 1));
+            match max_height {
+                0..=9 => heights.map(|height| height.to_string()).collect::<String>(),
+                _ => heights.map(|height| height.to_string()).join(" "),
+            }
+        })
+        .join("\n")
+}
+
+/// Brute-force references for the differential tests: every tree walks outwards in all four
+/// directions.
+pub mod reference {
+    use super::parse_grid;
+
+    /// For each direction from `(y, x)`, the trees up to and including the first one at
+    /// least as tall, and whether the walk reached the edge first.
+    fn views(grid: &[Vec<u32>], y: usize, x: usize) -> [(usize, bool); 4] {
+        let (rows, columns) = (grid.len(), grid[0].len());
+        let walks: [Vec<(usize, usize)>; 4] = [
+            (0..y).rev().map(|i| (i, x)).collect(),
+            (y + 1..rows).map(|i| (i, x)).collect(),
+            (0..x).rev().map(|i| (y, i)).collect(),
+            (x + 1..columns).map(|i| (y, i)).collect(),
+        ];
+        walks.map(
+            |walk| match walk.iter().position(|&(i, j)| grid[i][j] >= grid[y][x]) {
+                Some(blocker) => (blocker + 1, false),
+                None => (walk.len(), true),
+            },
+        )
+    }
+
+    pub fn part_one(input: &str) -> Option<usize> {
+        let grid = parse_grid(input)?;
+        let cells = (0..grid.len()).flat_map(|y| (0..grid[0].len()).map(move |x| (y, x)));
+        let visible = cells.filter(|&(y, x)| views(&grid, y, x).iter().any(|view| view.1));
+        Some(visible.count())
+    }
+
+    pub fn part_two(input: &str) -> Option<usize> {
+        let grid = parse_grid(input)?;
+        let cells = (0..grid.len()).flat_map(|y| (0..grid[0].len()).map(move |x| (y, x)));
+        cells
+            .map(|(y, x)| views(&grid, y, x).iter().map(|view| view.0).product())
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 8);
+        assert_eq!(part_one(&input), Some(21));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 8);
+        assert_eq!(part_two(&input), Some(8));
+    }
+
+    #[test]
+    fn test_survey() {
+        let input = crate::read_file("examples", 8);
+        let survey = survey(&parse_grid(&input).unwrap());
+        assert_eq!(survey.visibility_map(), "#####\n###.#\n##.##\n#.#.#\n#####");
+        assert_eq!(survey.scenic_heatmap(), "     \n -*- \n *-- \n -@* \n     ");
+
+        let input = "10 30 20\n25 5 40";
+        assert_eq!(part_one(input), Some(6));
+        assert_eq!(part_two(input), Some(0));
+    }
+
+    #[test]
+    fn test_reference() {
+        let generate = |rng: &mut Rng| {
+            let params = Params::default()
+                .with("width", 1 + rng.below(12))
+                .with("max_height", [3, 9, 40][rng.below(3)]);
+            let size = 1 + rng.below(12);
+            generate(size, rng, &params)
+        };
+        crate::differential::check(8, 300, generate, part_one, reference::part_one);
+        crate::differential::check(8, 300, generate, part_two, reference::part_two);
+    }
+}
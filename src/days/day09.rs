@@ -0,0 +1,352 @@
+use std::collections::HashSet;
+use std::io::BufRead;
+
+use crate::helpers::{stream_lines, GetInts, Rng, Simulation};
+use crate::{Param, Params};
+use itertools::Itertools;
+
+pub type Position = (isize, isize);
+
+pub fn part_one(input: &str) -> Option<usize> {
+    get_tail_visited_positions(input.lines().map(Some), &2)
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    get_tail_visited_positions(input.lines().map(Some), &10)
+}
+
+pub fn part_one_stream(input: impl BufRead) -> Option<usize> {
+    get_tail_visited_positions(stream_lines(input), &2)
+}
+
+pub fn part_two_stream(input: impl BufRead) -> Option<usize> {
+    get_tail_visited_positions(stream_lines(input), &10)
+}
+
+pub fn get_tail_visited_positions<L: AsRef<str>>(
+    motions: impl Iterator<Item = Option<L>>,
+    rope_length: &usize,
+) -> Option<usize> {
+    visited_counts(motions, *rope_length)?.pop()
+}
+
+/// How many positions each knot visits, from the head to the tail.
+pub fn visited_counts<L: AsRef<str>>(
+    motions: impl Iterator<Item = Option<L>>,
+    rope_length: usize,
+) -> Option<Vec<usize>> {
+    let mut rope = Rope::new(rope_length);
+    for motion in motions {
+        let (step, times) = parse_motion(motion?.as_ref())?;
+        for _ in 0..times {
+            rope.pull(step);
+        }
+    }
+    Some(rope.visited.iter().map(HashSet::len).collect())
+}
+
+/// The head's step and how many times it is taken, or `None` unless the line is e.g. `U 4`.
+/// Diagonal motions combine a vertical and a horizontal direction, as in `UR 4`.
+pub fn parse_motion(motion: &str) -> Option<(Position, usize)> {
+    let mut motion = motion.split_whitespace();
+    let direction = motion.next()?;
+    let mut step = (0, 0);
+    for letter in direction.chars() {
+        let (x, y) = match letter {
+            'U' => (0, 1),
+            'D' => (0, -1),
+            'R' => (1, 0),
+            'L' => (-1, 0),
+            _ => return None,
+        };
+        if (x != 0 && step.0 != 0) || (y != 0 && step.1 != 0) {
+            return None;
+        }
+        step = (step.0 + x, step.1 + y);
+    }
+    match direction.len() <= 2 {
+        true => Some((step, motion.next()?.parse().ok()?)),
+        false => None,
+    }
+}
+
+/// A rope of knots, each following the one before it, and where each of them has been.
+#[derive(Debug, Clone)]
+pub struct Rope {
+    /// From the head to the tail.
+    pub knots: Vec<Position>,
+    /// The positions each knot has visited, in the same order.
+    pub visited: Vec<HashSet<Position>>,
+}
+
+impl Rope {
+    pub fn new(length: usize) -> Self {
+        let length = length.max(1);
+        Rope {
+            knots: vec![(0, 0); length],
+            visited: vec![HashSet::from([(0, 0)]); length],
+        }
+    }
+
+    pub fn tail(&self) -> Position {
+        *self.knots.last().unwrap()
+    }
+
+    /// Moves the head by `step` and every other knot after it, and returns where they all are.
+    pub fn pull(&mut self, step: Position) -> &[Position] {
+        self.knots[0].0 += step.0;
+        self.knots[0].1 += step.1;
+        self.visited[0].insert(self.knots[0]);
+
+        for knot in 1..self.knots.len() {
+            let head_position = self.knots[knot - 1];
+            let tail_position = &mut self.knots[knot];
+
+            let delta = (
+                (head_position.0 - tail_position.0),
+                (head_position.1 - tail_position.1),
+            );
+            let not_touching = delta.0.abs() > 1 || delta.1.abs() > 1;
+
+            if not_touching {
+                tail_position.0 += delta.0.signum();
+                tail_position.1 += delta.1.signum();
+            }
+            self.visited[knot].insert(*tail_position);
+        }
+        &self.knots
+    }
+
+    /// The knots as the puzzle draws them, `H` then `T` or `1` to `9`, over `s` at the start
+    /// and, if `trail` is a knot, `#` where it has been. The area is `bounds`, its lowest and
+    /// highest corners, or just enough to show all of that.
+    pub fn render(&self, trail: Option<usize>, bounds: Option<(Position, Position)>) -> String {
+        self.draw(&self.knots, trail, bounds)
+    }
+
+    /// Where `knot` has been, as the puzzle draws it: `#`, with `s` at the start.
+    pub fn render_trail(&self, knot: usize, bounds: Option<(Position, Position)>) -> String {
+        self.draw(&[], Some(knot), bounds)
+    }
+
+    fn draw(
+        &self,
+        knots: &[Position],
+        trail: Option<usize>,
+        bounds: Option<(Position, Position)>,
+    ) -> String {
+        let label = |knot: usize| match knot {
+            0 => 'H',
+            _ if self.knots.len() == 2 => 'T',
+            _ => char::from_digit(knot as u32 % 10, 10).unwrap(),
+        };
+        let trail = trail.and_then(|knot| self.visited.get(knot));
+        let ((min_x, min_y), (max_x, max_y)) = bounds.unwrap_or_else(|| {
+            let all = || knots.iter().chain(trail.into_iter().flatten());
+            let (min_x, max_x) = all()
+                .map(|p| p.0)
+                .chain([0])
+                .minmax()
+                .into_option()
+                .unwrap();
+            let (min_y, max_y) = all()
+                .map(|p| p.1)
+                .chain([0])
+                .minmax()
+                .into_option()
+                .unwrap();
+            ((min_x, min_y), (max_x, max_y))
+        });
+        (min_y..=max_y)
+            .rev()
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| match knots.iter().position(|&knot| knot == (x, y)) {
+                        Some(knot) => label(knot),
+                        None if (x, y) == (0, 0) => 's',
+                        None if trail.is_some_and(|trail| trail.contains(&(x, y))) => '#',
+                        None => '.',
+                    })
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+}
+
+/// The rope pulled through all of the motions, one step of the head at a time.
+#[derive(Debug, Clone)]
+pub struct Motions {
+    pub rope: Rope,
+    steps: Vec<Position>,
+    taken: usize,
+}
+
+/// The motions of `input` on the rope of `part`, to step through.
+pub fn simulation(input: &str, part: u8) -> Option<Motions> {
+    let mut steps = vec![];
+    for motion in input.lines() {
+        let (step, times) = parse_motion(motion)?;
+        steps.extend(std::iter::repeat_n(step, times));
+    }
+    Some(Motions {
+        rope: Rope::new(if part == 1 { 2 } else { 10 }),
+        steps,
+        taken: 0,
+    })
+}
+
+impl Simulation for Motions {
+    type Snapshot = (Rope, usize);
+    const CONDITIONS: &'static str = "`head x,y`, `tail x,y`, `visited N`";
+
+    fn step(&mut self) -> bool {
+        let Some(step) = self.steps.get(self.taken) else {
+            return false;
+        };
+        self.rope.pull(*step);
+        self.taken += 1;
+        true
+    }
+
+    fn state(&self) -> String {
+        format!(
+            "head at {:?}, tail at {:?}, {} positions visited, {} steps to go",
+            self.rope.knots[0],
+            self.rope.tail(),
+            self.rope.visited.last().unwrap().len(),
+            self.steps.len() - self.taken
+        )
+    }
+
+    fn render(&self) -> String {
+        self.rope.render(Some(self.rope.knots.len() - 1), None)
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        (self.rope.clone(), self.taken)
+    }
+
+    fn restore(&mut self, (rope, taken): Self::Snapshot) {
+        self.rope = rope;
+        self.taken = taken;
+    }
+
+    fn holds(&self, condition: &str) -> Option<bool> {
+        match condition.split_once(' ')? {
+            ("head", at) => Some(self.rope.knots[0] == position(at)?),
+            ("tail", at) => Some(self.rope.tail() == position(at)?),
+            ("visited", count) => {
+                Some(self.rope.visited.last().unwrap().len() >= count.parse().ok()?)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn position(at: &str) -> Option<Position> {
+    let [x, y] = at.ints().ok()?;
+    Some((x, y))
+}
+
+/// Whether `generate` also moves the head diagonally.
+pub const PARAMS: &[Param] = &[Param::new::<bool>("diagonal", "false")];
+
+/// `size` random head motions of up to twenty steps, some of them diagonal if `diagonal` is
+/// set.
+pub fn generate(size: usize, rng: &mut Rng, params: &Params) -> String {
+    let directions: &[&str] = match params.get(PARAMS, "diagonal") {
+        true => &["U", "D", "L", "R", "UL", "UR", "DL", "DR"],
+        false => &["U", "D", "L", "R"],
+    };
+    (0..size.max(1))
+        .map(|_| format!("{} {}", rng.choose(directions), rng.between(1, 20)))
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 9);
+        assert_eq!(part_one(&input), Some(13));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 9);
+        assert_eq!(part_two(&input), Some(1));
+    }
+
+    #[test]
+    fn test_part_two_example_two() {
+        let input = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+        assert_eq!(part_two(input), Some(36));
+
+        let mut rope = Rope::new(10);
+        for (step, times) in input.lines().map(|motion| parse_motion(motion).unwrap()) {
+            for _ in 0..times {
+                rope.pull(step);
+            }
+        }
+        let trail = rope.render_trail(9, Some(((-11, -5), (14, 15))));
+        assert_eq!(
+            trail.lines().collect_vec()[9..],
+            [
+                "#.........................",
+                "#.............###.........",
+                "#............#...#........",
+                ".#..........#.....#.......",
+                "..#..........#.....#......",
+                "...#........#.......#.....",
+                "....#......s.........#....",
+                ".....#..............#.....",
+                "......#............#......",
+                ".......#..........#.......",
+                "........#........#........",
+                ".........########.........",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rope() {
+        let input = crate::read_file("examples", 9);
+        let counts = visited_counts(input.lines().map(Some), 10).unwrap();
+        // the first knot after the head moves just like the tail of a two-knot rope.
+        assert_eq!(counts[1..], [13, 7, 4, 3, 2, 1, 1, 1, 1]);
+
+        let mut rope = Rope::new(2);
+        for (step, times) in input.lines().map(|motion| parse_motion(motion).unwrap()) {
+            for _ in 0..times {
+                rope.pull(step);
+            }
+        }
+        let bounds = Some(((0, 0), (5, 4)));
+        assert_eq!(
+            rope.render(None, bounds),
+            "......\n......\n.TH...\n......\ns....."
+        );
+        assert_eq!(
+            rope.render_trail(1, bounds),
+            "..##..\n...##.\n.####.\n....#.\ns###.."
+        );
+
+        assert_eq!(parse_motion("UR 3"), Some(((1, 1), 3)));
+        assert_eq!(parse_motion("DL 1"), Some(((-1, -1), 1)));
+        assert_eq!(parse_motion("UD 1"), None);
+        assert_eq!(parse_motion("URR 1"), None);
+        let mut rope = Rope::new(3);
+        assert_eq!(rope.pull((1, 1)), [(1, 1), (0, 0), (0, 0)]);
+        assert_eq!(rope.pull((1, 1)), [(2, 2), (1, 1), (0, 0)]);
+        assert_eq!(rope.pull((1, 0)), [(3, 2), (2, 2), (1, 1)]);
+    }
+}
@@ -0,0 +1,652 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::BufRead;
+use std::rc::Rc;
+
+use crate::helpers::ocr::{ocr, SMALL};
+use crate::helpers::{stream_lines, Rng, Simulation};
+use crate::{Param, Params};
+use itertools::Itertools;
+
+pub fn part_one(input: &str) -> Option<usize> {
+    signal_strengths(input.lines().map(Some))
+}
+
+pub fn part_two(input: &str) -> Option<String> {
+    letters(&crt_image(input)?)
+}
+
+pub fn part_one_stream(input: impl BufRead) -> Option<usize> {
+    signal_strengths(stream_lines(input))
+}
+
+pub fn part_two_stream(input: impl BufRead) -> Option<String> {
+    letters(&render_crt(stream_lines(input))?)
+}
+
+/// The image drawn on the 40x6 screen, before reading its letters.
+pub fn crt_image(input: &str) -> Option<String> {
+    render_crt(input.lines().map(Some))
+}
+
+/// Sum of the signal strengths during the 20th, 60th, ... 220th cycles.
+fn signal_strengths<L: AsRef<str>>(commands: impl Iterator<Item = Option<L>>) -> Option<usize> {
+    let mut sampler = Sampler::new(20, 40, 220);
+    run(commands, &mut [&mut sampler])?;
+    // a negative `X` can make the total negative, which is not an answer.
+    usize::try_from(sampler.strengths).ok()
+}
+
+/// The image drawn on the 40x6 screen.
+fn render_crt<L: AsRef<str>>(commands: impl Iterator<Item = Option<L>>) -> Option<String> {
+    let mut crt = Crt::new(40, 6);
+    run(commands, &mut [&mut crt])?;
+    Some(crt.render())
+}
+
+/// The letters drawn on the screen; a blank screen has none.
+fn letters(image: &str) -> Option<String> {
+    ocr(image, &SMALL)
+        .ok()
+        .filter(|letters| !letters.is_empty())
+}
+
+/// Runs the program a line at a time, as it is read.
+fn run<L: AsRef<str>>(
+    commands: impl Iterator<Item = Option<L>>,
+    observers: &mut [&mut dyn Observer],
+) -> Option<Vm> {
+    let mut vm = Vm::new();
+    for command in commands {
+        vm.execute(&*decode(command?.as_ref())?, observers);
+    }
+    Some(vm)
+}
+
+/// Register values by name.
+pub type Registers = BTreeMap<String, isize>;
+
+/// The register the puzzle's CPU has.
+pub const X: &str = "X";
+
+pub trait Instruction: fmt::Debug {
+    fn name(&self) -> &str;
+
+    /// How many cycles the instruction takes.
+    fn cycles(&self) -> usize;
+
+    /// What the instruction does to the registers, once its last cycle is over.
+    fn execute(&self, registers: &mut Registers);
+
+    /// The instruction as a program would have it.
+    fn disassemble(&self) -> String {
+        self.name().to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Noop;
+
+impl Instruction for Noop {
+    fn name(&self) -> &str {
+        "noop"
+    }
+
+    fn cycles(&self) -> usize {
+        1
+    }
+
+    fn execute(&self, _registers: &mut Registers) {}
+}
+
+/// `addx V` adds `V` to `X`, and likewise for any other register, as in `addy V`.
+#[derive(Debug, Clone)]
+pub struct Add {
+    pub name: String,
+    pub register: String,
+    pub value: isize,
+}
+
+impl Instruction for Add {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn cycles(&self) -> usize {
+        2
+    }
+
+    fn execute(&self, registers: &mut Registers) {
+        *registers.entry(self.register.clone()).or_insert(0) += self.value;
+    }
+
+    fn disassemble(&self) -> String {
+        format!("{} {}", self.name, self.value)
+    }
+}
+
+/// The instruction on a line of a program, or `None` if it is not one.
+pub fn decode(command: &str) -> Option<Box<dyn Instruction>> {
+    match command.split_ascii_whitespace().collect_vec()[..] {
+        ["noop"] => Some(Box::new(Noop)),
+        [name, value] => {
+            let register = name.strip_prefix("add")?;
+            if register.is_empty() || !register.chars().all(|c| c.is_ascii_lowercase()) {
+                return None;
+            }
+            Some(Box::new(Add {
+                name: name.to_string(),
+                register: register.to_ascii_uppercase(),
+                value: value.parse().ok()?,
+            }))
+        }
+        _ => None,
+    }
+}
+
+/// Every instruction of `input`, with `decode` or another decoder for more of them.
+pub fn parse_program(
+    input: &str,
+    decode: impl Fn(&str) -> Option<Box<dyn Instruction>>,
+) -> Option<Vec<Box<dyn Instruction>>> {
+    input.lines().map(decode).collect()
+}
+
+/// Told about every cycle, with its number (from 1) and the registers during it.
+pub trait Observer {
+    fn observe(&mut self, cycle: usize, registers: &Registers);
+}
+
+impl<F: FnMut(usize, &Registers)> Observer for F {
+    fn observe(&mut self, cycle: usize, registers: &Registers) {
+        self(cycle, registers)
+    }
+}
+
+/// Adds up the signal strength, the cycle number times `X`, during cycle `first` and every
+/// `every` cycles after it until `last`.
+#[derive(Debug, Clone)]
+pub struct Sampler {
+    pub first: usize,
+    pub every: usize,
+    pub last: usize,
+    pub strengths: isize,
+}
+
+impl Sampler {
+    pub fn new(first: usize, every: usize, last: usize) -> Sampler {
+        Sampler {
+            first,
+            every,
+            last,
+            strengths: 0,
+        }
+    }
+}
+
+impl Observer for Sampler {
+    fn observe(&mut self, cycle: usize, registers: &Registers) {
+        let sampled = cycle >= self.first && (cycle - self.first).is_multiple_of(self.every);
+        if sampled && cycle <= self.last {
+            self.strengths += cycle as isize * registers.get(X).copied().unwrap_or(0);
+        }
+    }
+}
+
+/// A screen drawing a pixel a cycle, row by row, lit if the three-pixel sprite at `X` covers it.
+#[derive(Debug, Clone)]
+pub struct Crt {
+    pub width: usize,
+    pub pixels: Vec<bool>,
+}
+
+impl Crt {
+    pub fn new(width: usize, height: usize) -> Crt {
+        Crt {
+            width,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    pub fn render(&self) -> String {
+        self.pixels
+            .chunks(self.width)
+            .map(|row| {
+                row.iter()
+                    .map(|lit| if *lit { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+}
+
+impl Observer for Crt {
+    fn observe(&mut self, cycle: usize, registers: &Registers) {
+        let position = ((cycle - 1) % self.width) as isize;
+        if let Some(pixel) = self.pixels.get_mut(cycle - 1) {
+            *pixel = (position - registers.get(X).copied().unwrap_or(0)).abs() < 2;
+        }
+    }
+}
+
+/// The registers, and the cycles gone by.
+#[derive(Debug, Clone)]
+pub struct Vm {
+    pub registers: Registers,
+    pub cycle: usize,
+    /// How many cycles of the current instruction have gone by.
+    pub busy: usize,
+}
+
+impl Default for Vm {
+    fn default() -> Self {
+        Vm::new()
+    }
+}
+
+impl Vm {
+    /// The puzzle's CPU, with `X` at 1.
+    pub fn new() -> Vm {
+        Vm {
+            registers: Registers::from([(X.to_string(), 1)]),
+            cycle: 0,
+            busy: 0,
+        }
+    }
+
+    /// Runs a cycle of `instruction`, after which it is carried out if that was its last.
+    /// Returns whether it was.
+    pub fn tick(
+        &mut self,
+        instruction: &dyn Instruction,
+        observers: &mut [&mut dyn Observer],
+    ) -> bool {
+        if instruction.cycles() > 0 {
+            self.cycle += 1;
+            for observer in observers.iter_mut() {
+                observer.observe(self.cycle, &self.registers);
+            }
+            self.busy += 1;
+        }
+        if self.busy < instruction.cycles() {
+            return false;
+        }
+        instruction.execute(&mut self.registers);
+        self.busy = 0;
+        true
+    }
+
+    pub fn execute(&mut self, instruction: &dyn Instruction, observers: &mut [&mut dyn Observer]) {
+        while !self.tick(instruction, observers) {}
+    }
+}
+
+/// The program with the cycle each instruction starts on, one per line.
+pub fn disassemble(program: &[Box<dyn Instruction>]) -> String {
+    let mut cycle = 1;
+    program
+        .iter()
+        .map(|instruction| {
+            let line = format!("{:>6}  {}", cycle, instruction.disassemble());
+            cycle += instruction.cycles();
+            line
+        })
+        .join("\n")
+}
+
+/// Every cycle of running `program`: its number, the instruction and how far into it, and the
+/// registers during the cycle.
+pub fn trace(program: &[Box<dyn Instruction>]) -> String {
+    let mut vm = Vm::new();
+    let mut lines = vec![];
+    for instruction in program {
+        loop {
+            let registers = vm
+                .registers
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value));
+            let line = format!(
+                "{:>6}  {:<12} {}/{}  {}",
+                vm.cycle + 1,
+                instruction.disassemble(),
+                vm.busy + 1,
+                instruction.cycles(),
+                registers.format(" ")
+            );
+            let done = vm.tick(&**instruction, &mut []);
+            lines.push(line);
+            if done {
+                break;
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+/// The CPU running the program a cycle at a time, and the screen it draws on.
+#[derive(Debug, Clone)]
+pub struct Cpu {
+    program: Rc<[Box<dyn Instruction>]>,
+    /// The instruction being executed.
+    current: usize,
+    pub vm: Vm,
+    pub sampler: Sampler,
+    pub crt: Crt,
+}
+
+/// The CPU about to run `input`; both parts run the same program.
+pub fn simulation(input: &str) -> Option<Cpu> {
+    Some(Cpu {
+        program: parse_program(input, decode)?.into(),
+        current: 0,
+        vm: Vm::new(),
+        sampler: Sampler::new(20, 40, 220),
+        crt: Crt::new(40, 6),
+    })
+}
+
+impl Simulation for Cpu {
+    type Snapshot = Cpu;
+    const CONDITIONS: &'static str = "`x N` (or any register), `cycle N`";
+
+    fn step(&mut self) -> bool {
+        let Some(instruction) = self.program.get(self.current) else {
+            return false;
+        };
+        if self
+            .vm
+            .tick(&**instruction, &mut [&mut self.sampler, &mut self.crt])
+        {
+            self.current += 1;
+        }
+        true
+    }
+
+    fn state(&self) -> String {
+        format!(
+            "after cycle {}: {}, signal strengths {}, {} instructions to go",
+            self.vm.cycle,
+            self.vm
+                .registers
+                .iter()
+                .map(|(name, value)| format!("{} = {}", name, value))
+                .join(", "),
+            self.sampler.strengths,
+            self.program.len() - self.current
+        )
+    }
+
+    fn render(&self) -> String {
+        self.crt.render()
+    }
+
+    fn snapshot(&self) -> Cpu {
+        self.clone()
+    }
+
+    fn restore(&mut self, snapshot: Cpu) {
+        *self = snapshot;
+    }
+
+    fn holds(&self, condition: &str) -> Option<bool> {
+        match condition.split_once(' ')? {
+            ("cycle", cycle) => Some(self.vm.cycle == cycle.parse::<usize>().ok()?),
+            (register, value) => {
+                let current = self.vm.registers.get(&register.to_ascii_uppercase())?;
+                Some(*current == value.parse::<isize>().ok()?)
+            }
+        }
+    }
+}
+
+/// The letters `generate` draws, random ones if empty.
+pub const PARAMS: &[Param] = &[Param::new::<String>("letters", "")];
+
+/// A program drawing 8 random letters (or those of a `letters` param), then random instructions
+/// that keep `X` on the screen until there are `size`. As `X` starts at 1, the top left pixels are
+/// always lit, so only B, E, F, P, R and Z can come first.
+pub fn generate(size: usize, rng: &mut Rng, params: &Params) -> String {
+    let mut letters: String = params.get(PARAMS, "letters");
+    if letters.is_empty() {
+        let all = SMALL.letters().collect_vec();
+        let first = ['B', 'E', 'F', 'P', 'R', 'Z'];
+        letters.push(first[rng.below(first.len())]);
+        letters.extend((1..8).map(|_| all[rng.below(all.len())]));
+    }
+    let image = SMALL
+        .draw(&letters)
+        .filter(|_| letters.len() <= 8)
+        .unwrap_or_else(|| panic!("cannot draw `{}` on the screen", letters));
+    let mut program = draw(&image, rng).unwrap_or_else(|| panic!("no program draws `{}`", letters));
+
+    let mut x: isize = 1 + program
+        .iter()
+        .filter_map(|instruction| instruction.strip_prefix("addx "))
+        .map(|value| value.parse::<isize>().unwrap())
+        .sum::<isize>();
+    while program.len() < size {
+        let value = rng.between((-x).max(-20), (39 - x).min(20));
+        match value == 0 || rng.chance(1, 3) {
+            true => program.push("noop".to_string()),
+            false => {
+                x += value;
+                program.push(format!("addx {}", value));
+            }
+        }
+    }
+    program.join("\n")
+}
+
+/// A program drawing `image` (rows of `#` and `.`, at most 40x6) on the screen, found by going
+/// through every `X` that lights exactly the right pixel on each cycle, in random order.
+fn draw(image: &str, rng: &mut Rng) -> Option<Vec<String>> {
+    const CYCLES: usize = 240;
+    // `X` can leave the screen, where the sprite lights nothing.
+    const LOWEST: isize = -2;
+    let values = 44;
+    let mut lit = vec![false; CYCLES];
+    for (row, line) in image.lines().enumerate() {
+        for (column, pixel) in line.chars().enumerate() {
+            lit[row * 40 + column] = pixel == '#';
+        }
+    }
+    let fits = |cycle: usize, x: usize| {
+        let position = (cycle % 40) as isize;
+        lit[cycle] == ((position - (x as isize + LOWEST)).abs() < 2)
+    };
+
+    // how each reachable (cycle, `X`) at the start of an instruction was reached.
+    let mut from: Vec<Vec<Option<usize>>> = vec![vec![None; values]; CYCLES + 1];
+    from[0][(1 - LOWEST) as usize] = Some(0);
+    for cycle in 0..CYCLES {
+        let mut reached = (0..values)
+            .filter(|&x| from[cycle][x].is_some() && fits(cycle, x))
+            .collect_vec();
+        rng.shuffle(&mut reached);
+        for x in reached {
+            from[cycle + 1][x].get_or_insert(x);
+            if cycle + 1 < CYCLES && fits(cycle + 1, x) {
+                for next in (0..values).filter(|&next| next != x) {
+                    from[cycle + 2][next].get_or_insert(x);
+                }
+            }
+        }
+    }
+
+    let mut x = (0..values).find(|&x| from[CYCLES][x].is_some())?;
+    let mut cycle = CYCLES;
+    let mut program = vec![];
+    while cycle > 0 {
+        let previous = from[cycle][x]?;
+        // an `addx` always moves `X`, so staying put was a `noop`.
+        match previous == x {
+            true => {
+                program.push("noop".to_string());
+                cycle -= 1;
+            }
+            false => {
+                program.push(format!("addx {}", x as isize - previous as isize));
+                cycle -= 2;
+            }
+        }
+        x = previous;
+    }
+    program.reverse();
+    Some(program)
+}
+
+/// Plain cycle-by-cycle versions of both parts, to check the solvers against.
+pub mod reference {
+    use itertools::Itertools;
+
+    /// Value of `X` during each cycle, starting with the first, or `None` if a line is not an
+    /// instruction. `addy` and the like take two cycles and leave `X` alone.
+    fn register(input: &str) -> Option<Vec<isize>> {
+        let mut x = 1;
+        let mut values = vec![];
+        for instruction in input.lines() {
+            match instruction.split_ascii_whitespace().collect_vec()[..] {
+                ["noop"] => values.push(x),
+                [name, value] => {
+                    let register = name.strip_prefix("add")?;
+                    if register.is_empty() || !register.chars().all(|c| c.is_ascii_lowercase()) {
+                        return None;
+                    }
+                    let value = value.parse::<isize>().ok()?;
+                    values.extend([x, x]);
+                    if register == "x" {
+                        x += value;
+                    }
+                }
+                _ => return None,
+            }
+        }
+        Some(values)
+    }
+
+    pub fn part_one(input: &str) -> Option<usize> {
+        let register = register(input)?;
+        Some(
+            [20, 60, 100, 140, 180, 220]
+                .into_iter()
+                .filter_map(|cycle| Some(cycle * *register.get(cycle - 1)? as usize))
+                .sum(),
+        )
+    }
+
+    pub fn part_two(input: &str) -> Option<String> {
+        let letters = crate::helpers::ocr::ocr(&crt_image(input)?, &crate::helpers::ocr::SMALL);
+        letters.ok().filter(|letters| !letters.is_empty())
+    }
+
+    pub fn crt_image(input: &str) -> Option<String> {
+        let mut crt = vec!['.'; 40 * 6];
+        for (cycle, (pixel, x)) in crt.iter_mut().zip(register(input)?).enumerate() {
+            if ((cycle % 40) as isize - x).abs() <= 1 {
+                *pixel = '#';
+            }
+        }
+        Some(
+            crt.chunks(40)
+                .map(|row| row.iter().collect::<String>())
+                .join("\n"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 10);
+        assert_eq!(part_one(&input), Some(13140));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 10);
+        // the example draws no letters.
+        assert_eq!(part_two(&input), None);
+        let output = String::from(
+            "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....",
+        );
+        assert_eq!(crt_image(&input), Some(output));
+
+        let mut rng = Rng::new(10);
+        for letters in ["BACEFGHI", "PJKLORSU", "ZY"] {
+            let params = Params::default().with("letters", letters);
+            let program = generate(0, &mut rng, &params);
+            assert_eq!(part_two(&program), Some(letters.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_vm() {
+        let program = parse_program("noop\naddx 3\naddy -5", decode).unwrap();
+        assert_eq!(
+            disassemble(&program),
+            "     1  noop\n     2  addx 3\n     4  addy -5"
+        );
+        assert_eq!(
+            trace(&program),
+            [
+                "     1  noop         1/1  X=1",
+                "     2  addx 3       1/2  X=1",
+                "     3  addx 3       2/2  X=1",
+                "     4  addy -5      1/2  X=4",
+                "     5  addy -5      2/2  X=4",
+            ]
+            .join("\n")
+        );
+
+        #[derive(Debug)]
+        struct Double;
+        impl Instruction for Double {
+            fn name(&self) -> &str {
+                "double"
+            }
+            fn cycles(&self) -> usize {
+                3
+            }
+            fn execute(&self, registers: &mut Registers) {
+                *registers.get_mut(X).unwrap() *= 2;
+            }
+        }
+        let decode = |command: &str| match command {
+            "double" => Some(Box::new(Double) as Box<dyn Instruction>),
+            _ => decode(command),
+        };
+        let program = parse_program("addx 2\ndouble\naddx 1", decode).unwrap();
+        let mut vm = Vm::new();
+        let mut values = vec![];
+        let mut record = |_, registers: &Registers| values.push(registers[X]);
+        for instruction in &program {
+            vm.execute(&**instruction, &mut [&mut record]);
+        }
+        assert_eq!(values, [1, 1, 3, 3, 3, 6, 6]);
+        assert_eq!(vm.registers[X], 7);
+        assert_eq!(vm.registers.get("Y"), None);
+    }
+
+    #[test]
+    fn test_reference() {
+        let generate = |rng: &mut Rng| {
+            let size = rng.below(400);
+            generate(size, rng, &Params::default())
+        };
+        crate::differential::check(10, 200, generate, part_one, reference::part_one);
+        crate::differential::check(10, 200, generate, crt_image, reference::crt_image);
+        for input in ["addy 5\naddx 2", "addx", "addx two", "addX 1", "jmp 1"] {
+            assert_eq!(part_one(input), reference::part_one(input), "{}", input);
+            assert_eq!(crt_image(input), reference::crt_image(input), "{}", input);
+        }
+        crate::differential::check(10, 200, generate, part_two, reference::part_two);
+    }
+}
@@ -0,0 +1,765 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::iter::Peekable;
+use std::mem;
+use std::str::FromStr;
+
+use crate::helpers::{GetInts, Rng};
+use crate::{Param, Params};
+use itertools::Itertools;
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    pub items: VecDeque<usize>,
+    pub inspections: usize,
+    pub operation: Expression,
+    pub test: Test,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Operator {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Operator::Add => "+",
+            Operator::Subtract => "-",
+            Operator::Multiply => "*",
+            Operator::Divide => "/",
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Operator::Add | Operator::Subtract => 0,
+            Operator::Multiply | Operator::Divide => 1,
+        }
+    }
+}
+
+/// How an inspection changes the worry level: `old`, numbers, `+ - * /` and parentheses, with
+/// the usual precedence, e.g. `old * old`, `3 + old` or `(old - 1) / 2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expression {
+    Old,
+    Number(usize),
+    Binary(Box<Expression>, Operator, Box<Expression>),
+}
+
+impl Expression {
+    /// The new worry level, or `None` if it overflows, goes negative or divides by zero.
+    pub fn evaluate(&self, old: usize) -> Option<usize> {
+        match self {
+            Expression::Old => Some(old),
+            Expression::Number(number) => Some(*number),
+            Expression::Binary(left, operator, right) => {
+                let (left, right) = (left.evaluate(old)?, right.evaluate(old)?);
+                match operator {
+                    Operator::Add => left.checked_add(right),
+                    Operator::Subtract => left.checked_sub(right),
+                    Operator::Multiply => left.checked_mul(right),
+                    Operator::Divide => left.checked_div(right),
+                }
+            }
+        }
+    }
+
+    /// Whether the expression only adds and multiplies, so that the remainder of the new worry
+    /// level depends on nothing but the remainder of the old one.
+    pub fn is_modular(&self) -> bool {
+        match self {
+            Expression::Old | Expression::Number(_) => true,
+            Expression::Binary(left, operator, right) => {
+                matches!(operator, Operator::Add | Operator::Multiply)
+                    && left.is_modular()
+                    && right.is_modular()
+            }
+        }
+    }
+
+    /// The new worry level modulo `modulus`, for a modular expression.
+    fn evaluate_modulo(&self, old: usize, modulus: usize) -> usize {
+        match self {
+            Expression::Old => old % modulus,
+            Expression::Number(number) => number % modulus,
+            Expression::Binary(left, operator, right) => {
+                let left = left.evaluate_modulo(old, modulus) as u128;
+                let right = right.evaluate_modulo(old, modulus) as u128;
+                let result = match operator {
+                    Operator::Add => left + right,
+                    Operator::Multiply => left * right,
+                    Operator::Subtract | Operator::Divide => unreachable!("not modular"),
+                };
+                (result % modulus as u128) as usize
+            }
+        }
+    }
+
+    /// `expression := product (("+" | "-") product)*`
+    fn parse_sum(tokens: &mut Tokens) -> Option<Expression> {
+        let mut sum = Expression::parse_product(tokens)?;
+        while let Some(operator) = tokens.next_if(|token| matches!(*token, "+" | "-")) {
+            let operator = match operator {
+                "+" => Operator::Add,
+                _ => Operator::Subtract,
+            };
+            let right = Expression::parse_product(tokens)?;
+            sum = Expression::Binary(Box::new(sum), operator, Box::new(right));
+        }
+        Some(sum)
+    }
+
+    /// `product := operand (("*" | "/") operand)*`
+    fn parse_product(tokens: &mut Tokens) -> Option<Expression> {
+        let mut product = Expression::parse_operand(tokens)?;
+        while let Some(operator) = tokens.next_if(|token| matches!(*token, "*" | "/")) {
+            let operator = match operator {
+                "*" => Operator::Multiply,
+                _ => Operator::Divide,
+            };
+            let right = Expression::parse_operand(tokens)?;
+            product = Expression::Binary(Box::new(product), operator, Box::new(right));
+        }
+        Some(product)
+    }
+
+    /// `operand := "old" | number | "(" expression ")"`
+    fn parse_operand(tokens: &mut Tokens) -> Option<Expression> {
+        match tokens.next()? {
+            "old" => Some(Expression::Old),
+            "(" => {
+                let expression = Expression::parse_sum(tokens)?;
+                tokens.next_if_eq(&")")?;
+                Some(expression)
+            }
+            number => number.parse().ok().map(Expression::Number),
+        }
+    }
+}
+
+type Tokens<'a> = Peekable<std::vec::IntoIter<&'a str>>;
+
+/// Splits an expression into words, numbers and single symbols, whatever the spacing.
+fn tokenize(expression: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    let mut rest = expression.trim_start();
+    while let Some(first) = rest.chars().next() {
+        let length = match first.is_ascii_alphanumeric() {
+            true => rest
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(rest.len()),
+            false => first.len_utf8(),
+        };
+        tokens.push(&rest[..length]);
+        rest = rest[length..].trim_start();
+    }
+    tokens
+}
+
+impl FromStr for Expression {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = tokenize(s).into_iter().peekable();
+        let expression = Expression::parse_sum(&mut tokens).ok_or(())?;
+        match tokens.next() {
+            None => Ok(expression),
+            Some(_) => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::Old => write!(f, "old"),
+            Expression::Number(number) => write!(f, "{}", number),
+            Expression::Binary(left, operator, right) => {
+                let precedence = |expression: &Expression| match expression {
+                    Expression::Binary(_, operator, _) => operator.precedence(),
+                    _ => u8::MAX,
+                };
+                // operators are left-associative, so only a right operand of the same
+                // precedence needs parentheses.
+                let left = match precedence(left) < operator.precedence() {
+                    true => format!("({})", left),
+                    false => left.to_string(),
+                };
+                let right = match precedence(right) <= operator.precedence() {
+                    true => format!("({})", right),
+                    false => right.to_string(),
+                };
+                write!(f, "{} {} {}", left, operator.symbol(), right)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Test {
+    pub divisible_by: usize,
+    pub return_if_true: usize,
+    pub return_if_false: usize,
+}
+
+impl Test {
+    pub fn result(&self, input: &usize) -> usize {
+        match input % self.divisible_by {
+            0 => self.return_if_true,
+            _ => self.return_if_false,
+        }
+    }
+}
+
+/// How the worry level falls after each inspection, as the `relief_*` params: `/3`, or `none`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relief {
+    Divide(usize),
+    None,
+}
+
+impl FromStr for Relief {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "none" {
+            return Ok(Relief::None);
+        }
+        match s.strip_prefix('/').map(str::parse) {
+            Some(Ok(divisor)) if divisor > 0 => Ok(Relief::Divide(divisor)),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The monkeys throwing items around, round after round.
+#[derive(Debug, Clone)]
+pub struct KeepAway {
+    pub monkeys: Vec<Monkey>,
+    pub relief: Relief,
+    /// What worry levels are kept modulo, when that changes no test: the LCM of the divisors,
+    /// if there is no relief and every operation is modular.
+    pub modulus: Option<usize>,
+}
+
+impl KeepAway {
+    pub fn new(monkeys: Vec<Monkey>, relief: Relief) -> KeepAway {
+        let modular =
+            relief == Relief::None && monkeys.iter().all(|monkey| monkey.operation.is_modular());
+        let modulus = monkeys
+            .iter()
+            .map(|monkey| monkey.test.divisible_by)
+            .try_fold(1, lcm)
+            .filter(|_| modular);
+        KeepAway {
+            monkeys,
+            relief,
+            modulus,
+        }
+    }
+
+    /// Plays a round: each monkey in turn inspects and throws all its items. `None` if a worry
+    /// level cannot be worked out.
+    pub fn round(&mut self) -> Option<()> {
+        for idx in 0..self.monkeys.len() {
+            let items = mem::take(&mut self.monkeys[idx].items);
+            self.monkeys[idx].inspections += items.len();
+            for item in items {
+                let (target, worry) = self.throw(idx, item)?;
+                self.monkeys[target].items.push_back(worry);
+            }
+        }
+        Some(())
+    }
+
+    /// Where monkey `idx` throws an item it inspects, with the item's new worry level.
+    fn throw(&self, idx: usize, item: usize) -> Option<(usize, usize)> {
+        let monkey = &self.monkeys[idx];
+        let worry = match self.modulus {
+            Some(modulus) => monkey.operation.evaluate_modulo(item, modulus),
+            None => monkey.operation.evaluate(item)?,
+        };
+        let worry = match self.relief {
+            Relief::Divide(divisor) => worry / divisor,
+            Relief::None => worry,
+        };
+        Some((monkey.test.result(&worry), worry))
+    }
+
+    /// Follows an item on its own for up to `rounds` rounds, or until it starts a round where it
+    /// started an earlier one: held by the same monkey, with the same worry level.
+    fn journey(&self, holder: usize, item: usize, rounds: usize) -> Option<Journey> {
+        let mut started = HashMap::new();
+        let mut journey = Journey::default();
+        let (mut holder, mut worry) = (holder, self.modulus.map_or(item, |m| item % m));
+        for round in 0..rounds {
+            if let Some(&start) = started.get(&(holder, worry)) {
+                journey.repeats_from = Some(start);
+                break;
+            }
+            started.insert((holder, worry), round);
+            journey.rounds.push(journey.inspectors.len());
+            loop {
+                journey.inspectors.push(holder);
+                let target;
+                (target, worry) = self.throw(holder, worry)?;
+                // monkeys later in the order inspect the item again in the same round.
+                let same_round = target > holder;
+                holder = target;
+                if !same_round {
+                    break;
+                }
+            }
+        }
+        journey.rounds.push(journey.inspectors.len());
+        Some(journey)
+    }
+
+    /// Each monkey's inspections after each of `rounds`, without playing them: items never
+    /// meet, so each one is followed on its own until its journey repeats, which is then
+    /// extrapolated. `None` if a worry level cannot be worked out.
+    pub fn inspections(&self, rounds: &[usize]) -> Option<Vec<Vec<usize>>> {
+        let longest = rounds.iter().copied().max().unwrap_or(0);
+        let mut counts = vec![vec![0; self.monkeys.len()]; rounds.len()];
+        let mut journeys = HashMap::new();
+        for (holder, monkey) in self.monkeys.iter().enumerate() {
+            for &item in &monkey.items {
+                let journey = match journeys.entry((holder, item)) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(self.journey(holder, item, longest)?),
+                };
+                for (counts, &rounds) in counts.iter_mut().zip(rounds) {
+                    journey.count(rounds, counts);
+                }
+            }
+        }
+        // the monkeys may have inspected items already.
+        for counts in &mut counts {
+            for (count, monkey) in counts.iter_mut().zip(&self.monkeys) {
+                *count += monkey.inspections;
+            }
+        }
+        Some(counts)
+    }
+
+    pub fn play(&mut self, rounds: usize) -> Option<()> {
+        (0..rounds).try_for_each(|_| self.round())
+    }
+
+    pub fn monkey_business(&self) -> u128 {
+        business(self.monkeys.iter().map(|monkey| monkey.inspections))
+    }
+}
+
+/// The monkeys inspecting an item, round after round.
+#[derive(Debug, Clone, Default)]
+struct Journey {
+    inspectors: Vec<usize>,
+    /// Where each round starts in `inspectors`, then where the last one ends.
+    rounds: Vec<usize>,
+    /// The round the journey goes back to after its last one, if it was followed that far.
+    repeats_from: Option<usize>,
+}
+
+impl Journey {
+    /// Adds the inspections of the first `rounds` rounds to `counts`, by monkey.
+    fn count(&self, rounds: usize, counts: &mut [usize]) {
+        let mut add = |from: usize, to: usize, times: usize| {
+            for &monkey in &self.inspectors[self.rounds[from]..self.rounds[to]] {
+                counts[monkey] += times;
+            }
+        };
+        let followed = self.rounds.len() - 1;
+        match self.repeats_from {
+            Some(start) if rounds > followed => {
+                let cycle = followed - start;
+                add(0, start, 1);
+                add(start, followed, (rounds - start) / cycle);
+                add(start, start + (rounds - start) % cycle, 1);
+            }
+            _ => add(0, rounds.min(followed), 1),
+        }
+    }
+}
+
+/// The product of the two highest inspection counts, which can outgrow a `usize`.
+fn business(inspections: impl Iterator<Item = usize>) -> u128 {
+    inspections
+        .sorted_by_key(|&inspections| Reverse(inspections))
+        .take(2)
+        .map(|inspections| inspections as u128)
+        .product()
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// `None` if it overflows.
+fn lcm(a: usize, b: usize) -> Option<usize> {
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// The rounds each part plays, and how worry falls after each inspection: `/N` or `none`.
+pub const PARAMS: &[Param] = &[
+    Param::new::<usize>("rounds_one", "20"),
+    Param::new::<Relief>("relief_one", "/3"),
+    Param::new::<usize>("rounds_two", "10000"),
+    Param::new::<Relief>("relief_two", "none"),
+];
+
+pub fn part_one(input: &str, params: &Params) -> Option<u128> {
+    let relief = params.get(PARAMS, "relief_one");
+    monkey_business(input, params.get(PARAMS, "rounds_one"), relief)
+}
+
+pub fn part_two(input: &str, params: &Params) -> Option<u128> {
+    let relief = params.get(PARAMS, "relief_two");
+    monkey_business(input, params.get(PARAMS, "rounds_two"), relief)
+}
+
+fn monkey_business(input: &str, rounds: usize, relief: Relief) -> Option<u128> {
+    let game = KeepAway::new(parse_input(input)?, relief);
+    Some(business(game.inspections(&[rounds])?.pop()?.into_iter()))
+}
+
+/// Each monkey's inspections every `every` rounds, with the part two rules unless the params
+/// say otherwise, in the puzzle's words.
+pub fn inspection_report(input: &str, params: &Params, every: usize) -> Option<String> {
+    if every == 0 {
+        return None;
+    }
+    let game = KeepAway::new(parse_input(input)?, params.get(PARAMS, "relief_two"));
+    let rounds = (every..=params.get(PARAMS, "rounds_two"))
+        .step_by(every)
+        .collect_vec();
+    let reports = rounds
+        .iter()
+        .zip(game.inspections(&rounds)?)
+        .map(|(round, counts)| {
+            let counts = counts
+                .iter()
+                .enumerate()
+                .map(|(idx, count)| format!("Monkey {} inspected items {} times.", idx, count));
+            format!("== After round {} ==\n{}", round, counts.format("\n"))
+        })
+        .join("\n\n");
+    Some(reports)
+}
+
+/// The monkeys, or `None` if a note is malformed, a test divides by zero or a monkey throws to
+/// one that does not exist.
+pub fn parse_input(input: &str) -> Option<Vec<Monkey>> {
+    let monkeys = input
+        .split("\n\n")
+        .map(|monkey_lines| {
+            let monkey_lines = monkey_lines.lines().collect_vec();
+            let items = monkey_lines
+                .get(1)?
+                .ints_iter()
+                .collect::<Result<_, _>>()
+                .ok()?;
+
+            let operation = monkey_lines.get(2)?.split(" = ").nth(1)?.parse().ok()?;
+
+            let [divisible_by] = monkey_lines.get(3)?.ints().ok()?;
+            let [return_if_true] = monkey_lines.get(4)?.ints().ok()?;
+            let [return_if_false] = monkey_lines.get(5)?.ints().ok()?;
+            let test = Test {
+                divisible_by,
+                return_if_true,
+                return_if_false,
+            };
+
+            Some(Monkey {
+                items,
+                inspections: 0,
+                operation,
+                test,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let valid = monkeys.iter().all(|monkey| {
+        monkey.test.divisible_by > 0
+            && monkey.test.return_if_true < monkeys.len()
+            && monkey.test.return_if_false < monkeys.len()
+    });
+    valid.then_some(monkeys)
+}
+
+/// Two to eight monkeys with distinct divisors, not always co-prime, holding `size` items
+/// between them. Items whose worry level would overflow within the part one rounds are redrawn.
+pub fn generate(size: usize, rng: &mut Rng, params: &Params) -> String {
+    let rounds: usize = params.get(PARAMS, "rounds_one");
+    'monkeys: loop {
+        let mut divisors = (2..=23).collect_vec();
+        rng.shuffle(&mut divisors);
+        let count = rng.between(2, 8) as usize;
+        let mut monkeys = (0..count)
+            .map(|idx| {
+                let operation = match rng.below(8) {
+                    0 => "old * old".to_string(),
+                    1 => "old + old".to_string(),
+                    2..=4 => format!("old * {}", rng.between(2, 19)),
+                    _ => format!("old + {}", rng.between(1, 8)),
+                };
+                // either operand order.
+                let operation = match rng.chance(1, 2) {
+                    true => operation.split(' ').rev().join(" "),
+                    false => operation,
+                };
+                let mut targets = (0..count).filter(|target| *target != idx).collect_vec();
+                rng.shuffle(&mut targets);
+                Monkey {
+                    items: VecDeque::new(),
+                    inspections: 0,
+                    operation: operation.parse().unwrap(),
+                    test: Test {
+                        divisible_by: divisors[idx],
+                        return_if_true: targets[0],
+                        return_if_false: *targets.last().unwrap(),
+                    },
+                }
+            })
+            .collect_vec();
+
+        for _ in 0..size {
+            let mut attempts = 0;
+            let (holder, item) = loop {
+                let (holder, item) = (rng.below(count), rng.between(50, 99) as usize);
+                if !overflows(&monkeys, holder, item, rounds) {
+                    break (holder, item);
+                }
+                attempts += 1;
+                if attempts == 100 {
+                    continue 'monkeys;
+                }
+            };
+            monkeys[holder].items.push_back(item);
+        }
+        return monkeys.iter().enumerate().map(describe).join("\n\n");
+    }
+}
+
+/// Follows a single item through `rounds` rounds of part one.
+fn overflows(monkeys: &[Monkey], mut holder: usize, mut item: usize, rounds: usize) -> bool {
+    for _round in 0..rounds {
+        loop {
+            let Some(worry) = monkeys[holder].operation.evaluate(item) else {
+                return true;
+            };
+            item = worry / 3;
+            let target = monkeys[holder].test.result(&item);
+            // monkeys later in the order inspect the item again in the same round.
+            let same_round = target > holder;
+            holder = target;
+            if !same_round {
+                break;
+            }
+        }
+    }
+    false
+}
+
+fn describe((idx, monkey): (usize, &Monkey)) -> String {
+    [
+        format!("Monkey {}:", idx),
+        format!("  Starting items: {}", monkey.items.iter().join(", ")),
+        format!("  Operation: new = {}", monkey.operation),
+        format!("  Test: divisible by {}", monkey.test.divisible_by),
+        format!(
+            "    If true: throw to monkey {}",
+            monkey.test.return_if_true
+        ),
+        format!(
+            "    If false: throw to monkey {}",
+            monkey.test.return_if_false
+        ),
+    ]
+    .join("\n")
+}
+
+/// Round-by-round versions of both parts, to check the per-item solver against.
+pub mod reference {
+    use super::{parse_input, KeepAway, Relief, PARAMS};
+    use crate::Params;
+
+    pub fn part_one(input: &str, params: &Params) -> Option<u128> {
+        let relief = params.get(PARAMS, "relief_one");
+        play(input, params.get(PARAMS, "rounds_one"), relief)
+    }
+
+    pub fn part_two(input: &str, params: &Params) -> Option<u128> {
+        let relief = params.get(PARAMS, "relief_two");
+        play(input, params.get(PARAMS, "rounds_two"), relief)
+    }
+
+    fn play(input: &str, rounds: usize, relief: Relief) -> Option<u128> {
+        let mut game = KeepAway::new(parse_input(input)?, relief);
+        game.play(rounds)?;
+        Some(game.monkey_business())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 11);
+        assert_eq!(part_one(&input, &Params::default()), Some(10605));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 11);
+        assert_eq!(part_two(&input, &Params::default()), Some(2713310158));
+    }
+
+    #[test]
+    fn test_expression() {
+        let parse = |s: &str| s.parse::<Expression>().unwrap();
+        for (expression, old, new) in [
+            ("old * old", 7, Some(49)),
+            ("19 * old", 2, Some(38)),
+            ("old + old", 4, Some(8)),
+            ("3+old*2", 5, Some(13)),
+            ("(3 + old) * 2", 5, Some(16)),
+            ("old - 2 - 1", 5, Some(2)),
+            ("old - (2 - 1)", 5, Some(4)),
+            ("(old - 1) / 2", 9, Some(4)),
+            ("old - 6", 5, None),
+            ("old / (old - old)", 5, None),
+        ] {
+            assert_eq!(parse(expression).evaluate(old), new, "{}", expression);
+            assert_eq!(parse(&parse(expression).to_string()), parse(expression));
+        }
+        assert_eq!(parse("(3+old)*(old)").to_string(), "(3 + old) * old");
+        assert_eq!(parse("old - (2 - 1)").to_string(), "old - (2 - 1)");
+        assert!(parse("2 * (old + 1)").is_modular());
+        assert!(!parse("old * old / 2").is_modular());
+        for invalid in [
+            "",
+            "old *",
+            "old + + 1",
+            "(old",
+            "old old",
+            "new * 2",
+            "old % 2",
+        ] {
+            assert!(invalid.parse::<Expression>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_keep_away() {
+        let input = crate::read_file("examples", 11);
+        let monkeys = parse_input(&input).unwrap();
+        // the example's divisors are co-prime, so their LCM is their product.
+        let game = KeepAway::new(monkeys.clone(), Relief::None);
+        assert_eq!(game.modulus, Some(23 * 19 * 13 * 17));
+        assert_eq!(
+            KeepAway::new(monkeys.clone(), Relief::Divide(3)).modulus,
+            None
+        );
+
+        let input = input
+            .replace("divisible by 19", "divisible by 46")
+            .replace("divisible by 13", "divisible by 26")
+            .replace("new = old * 19", "new = 19 * old")
+            .replace("new = old + 6", "new = old + old");
+        let mut game = KeepAway::new(parse_input(&input).unwrap(), Relief::None);
+        assert_eq!(game.modulus, Some(23 * 2 * 13 * 17));
+        game.play(1000).unwrap();
+        let mut exact = KeepAway::new(parse_input(&input).unwrap(), Relief::None);
+        exact.modulus = None;
+        assert_eq!(exact.play(1000), None);
+        exact.monkeys = parse_input(&input).unwrap();
+        exact.play(3).unwrap();
+        let mut reduced = KeepAway::new(parse_input(&input).unwrap(), Relief::None);
+        reduced.play(3).unwrap();
+        let inspections =
+            |game: &KeepAway| game.monkeys.iter().map(|m| m.inspections).collect_vec();
+        assert_eq!(inspections(&reduced), inspections(&exact));
+
+        let input = input.replace("new = old + 3", "new = old - 3");
+        assert_eq!(
+            KeepAway::new(parse_input(&input).unwrap(), Relief::None).modulus,
+            None
+        );
+
+        assert_eq!("/3".parse(), Ok(Relief::Divide(3)));
+        assert_eq!("none".parse(), Ok(Relief::None));
+        assert_eq!("/0".parse::<Relief>(), Err(()));
+        let params = Params::default()
+            .with("relief_one", "none")
+            .with("rounds_one", 1000);
+        assert_eq!(
+            part_one(&crate::read_file("examples", 11), &params),
+            Some(27019168)
+        );
+    }
+
+    #[test]
+    fn test_inspections() {
+        let input = crate::read_file("examples", 11);
+        let game = KeepAway::new(parse_input(&input).unwrap(), Relief::None);
+        let counts = game.inspections(&[20, 1_000_000_000]).unwrap();
+        assert_eq!(counts[0], [99, 97, 8, 103]);
+        assert_eq!(counts[1], [5217653496, 4782346500, 193256586, 5202028499]);
+        let params = Params::default().with("rounds_two", 1_000_000_000);
+        assert_eq!(part_two(&input, &params), Some(5217653496 * 5202028499));
+
+        let report = inspection_report(&input, &Params::default(), 5000).unwrap();
+        assert_eq!(
+            report.lines().take(5).join("\n"),
+            [
+                "== After round 5000 ==",
+                "Monkey 0 inspected items 26075 times.",
+                "Monkey 1 inspected items 23921 times.",
+                "Monkey 2 inspected items 974 times.",
+                "Monkey 3 inspected items 26000 times.",
+            ]
+            .join("\n")
+        );
+        assert_eq!(report.lines().nth(6), Some("== After round 10000 =="));
+    }
+
+    #[test]
+    fn test_reference() {
+        let generate = |rng: &mut Rng| {
+            let size = 1 + rng.below(12);
+            generate(size, rng, &Params::default())
+        };
+        let params = Params::default()
+            .with("rounds_one", 1000)
+            .with("rounds_two", 1000);
+        let divided = params
+            .clone()
+            .with("relief_one", "/3")
+            .with("relief_two", "/3");
+        for params in [params, divided] {
+            let part_one = |input: &str| part_one(input, &params);
+            let part_two = |input: &str| part_two(input, &params);
+            crate::differential::check(11, 100, generate, part_one, |input: &str| {
+                reference::part_one(input, &params)
+            });
+            crate::differential::check(11, 100, generate, part_two, |input: &str| {
+                reference::part_two(input, &params)
+            });
+        }
+    }
+}
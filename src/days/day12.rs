@@ -0,0 +1,199 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::helpers::Rng;
+use crate::Params;
+use itertools::Itertools;
+
+pub type Position = (usize, usize);
+
+#[derive(PartialEq, Eq, Debug)]
+struct AStar {
+    node: Position,
+    f: usize,
+    g: usize,
+}
+
+impl Ord for AStar {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.f.cmp(&self.f)
+    }
+}
+
+impl PartialOrd for AStar {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+const VALID_POSITION_DELTAS: [(isize, isize); 4] = [(0, -1), (-1, 0), (0, 1), (1, 0)];
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let (map, start, end) = parse_input(input)?;
+    a_star(&map, &start, &end)
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let (map, _, end) = parse_input(input)?;
+    let mut candidates: Vec<Position> = vec![];
+
+    for (m, row) in map.iter().enumerate() {
+        for (n, square) in row.iter().enumerate() {
+            if *square == 'a' {
+                candidates.push((m, n));
+            }
+        }
+    }
+
+    let mut min = usize::MAX;
+    for candidate in &candidates {
+        if let Some(result) = a_star(&map, candidate, &end) {
+            if result < min {
+                min = result;
+            }
+        }
+    }
+
+    (min < usize::MAX).then_some(min)
+}
+
+/// The heightmap with its start and end, or `None` unless it is a rectangle of lowercase
+/// heights with an `S` and an `E`.
+pub fn parse_input(input: &str) -> Option<(Vec<Vec<char>>, Position, Position)> {
+    let mut map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let width = map.first()?.len();
+    if map.iter().any(|row| row.len() != width) {
+        return None;
+    }
+
+    let mut start = None;
+    let mut end = None;
+    for (m, row) in map.iter().enumerate() {
+        for (n, square) in row.iter().enumerate() {
+            match square {
+                'S' => start = Some((m, n)),
+                'E' => end = Some((m, n)),
+                'a'..='z' => (),
+                _ => return None,
+            }
+        }
+    }
+    let (start, end): (Position, Position) = (start?, end?);
+    map[start.0][start.1] = 'a';
+    map[end.0][end.1] = 'z';
+    Some((map, start, end))
+}
+
+pub fn a_star(map: &[Vec<char>], start: &Position, end: &Position) -> Option<usize> {
+    let mut open: BinaryHeap<AStar> = BinaryHeap::from([AStar {
+        node: *start,
+        f: 0,
+        g: 0,
+    }]);
+    let (m_max, n_max) = (map.len(), map[0].len());
+
+    let mut open_map: HashMap<Position, usize> = HashMap::from([(*start, 0)]);
+    let mut closed: HashSet<Position> = HashSet::new();
+
+    while !open.is_empty() {
+        let q = open.pop().unwrap();
+        open_map.remove(&q.node);
+        closed.insert(q.node);
+
+        if &q.node == end {
+            return Some(q.f);
+        }
+
+        for delta in VALID_POSITION_DELTAS {
+            let sucessor_pos = (q.node.0 as isize + delta.0, q.node.1 as isize + delta.1);
+            if sucessor_pos.0 < 0
+                || sucessor_pos.1 < 0
+                || sucessor_pos.0 >= m_max as isize
+                || sucessor_pos.1 >= n_max as isize
+            {
+                continue;
+            }
+            let sucessor_pos = (sucessor_pos.0 as usize, sucessor_pos.1 as usize);
+            if closed.contains(&sucessor_pos) {
+                continue;
+            }
+            if (map[sucessor_pos.0][sucessor_pos.1] as i8 - map[q.node.0][q.node.1] as i8) > 1 {
+                continue;
+            }
+
+            let g = q.g + 1;
+            let h: usize = ((sucessor_pos.0 as isize - end.0 as isize).abs()
+                + (sucessor_pos.1 as isize - end.1 as isize).abs())
+                as usize;
+            let f = g + h;
+
+            if let Some(open_node) = open_map.get(&sucessor_pos) {
+                if open_node < &f {
+                    continue;
+                }
+            }
+
+            open.push(AStar {
+                node: sucessor_pos,
+                f,
+                g,
+            });
+            open_map.insert(sucessor_pos, f);
+        }
+    }
+
+    None
+}
+
+/// A heightmap of `size` rows whose even rows, joined at alternating ends, climb steadily
+/// from `S` to `E`. Every other square gets a random height.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    let rows = size.max(3) | 1;
+    let columns = (2 * size).max(13);
+    let mut map = (0..rows)
+        .map(|_| {
+            (0..columns)
+                .map(|_| (b'a' + rng.below(26) as u8) as char)
+                .collect_vec()
+        })
+        .collect_vec();
+
+    let mut path = vec![];
+    for row in (0..rows).step_by(2) {
+        let mut cells = (0..columns).map(|column| (row, column)).collect_vec();
+        if row % 4 == 2 {
+            cells.reverse();
+        }
+        let end = cells.last().unwrap().1;
+        path.extend(cells);
+        if row + 1 < rows {
+            path.push((row + 1, end));
+        }
+    }
+    for (step, (row, column)) in path.iter().enumerate() {
+        map[*row][*column] = (b'a' + (25 * step / (path.len() - 1)) as u8) as char;
+    }
+    let (start, end) = (path[0], path[path.len() - 1]);
+    map[start.0][start.1] = 'S';
+    map[end.0][end.1] = 'E';
+
+    map.iter()
+        .map(|row| row.iter().collect::<String>())
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 12);
+        assert_eq!(part_one(&input), Some(31));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 12);
+        assert_eq!(part_two(&input), Some(29));
+    }
+}
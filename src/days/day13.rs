@@ -0,0 +1,132 @@
+use std::cmp::Ordering;
+
+use crate::helpers::Rng;
+use crate::Params;
+use itertools::Itertools;
+use serde_json::Value;
+
+/// Orders two packets: integers by value, lists item by item and then by length, and an
+/// integer against a list as a list of just that integer. Any other JSON value is taken as an
+/// integer above all the others, ordered among its kind by its JSON text, so every two values
+/// have an order.
+pub fn cmp_value(left: &Value, right: &Value) -> Ordering {
+    match (left, right) {
+        (Value::Array(l_self), Value::Array(l_other)) => l_self
+            .iter()
+            .zip(l_other)
+            .map(|(s, o)| cmp_value(s, o))
+            .find(|order| order.is_ne())
+            .unwrap_or_else(|| l_self.len().cmp(&l_other.len())),
+        (Value::Array(_), n_other) => cmp_value(left, &Value::Array(vec![n_other.clone()])),
+        (n_self, Value::Array(_)) => cmp_value(&Value::Array(vec![n_self.clone()]), right),
+        (n_self, n_other) => match (n_self.as_u64(), n_other.as_u64()) {
+            (Some(n_self), Some(n_other)) => n_self.cmp(&n_other),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => n_self.to_string().cmp(&n_other.to_string()),
+        },
+    }
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let mut sum = 0;
+    for (idx, block) in (1..).zip(input.split("\n\n")) {
+        let mut block = block.lines();
+        let packet1 = block.next();
+        let packet1 = parse_packet(packet1)?;
+        let packet2 = block.next();
+        let packet2 = parse_packet(packet2)?;
+        if cmp_value(&packet1, &packet2) == Ordering::Less {
+            sum += idx;
+        }
+    }
+    Some(sum) 
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let input = format!("{}\n\n[[2]]\n[[6]]", input);
+
+    let sorted = input.lines().filter(|f| !f.is_empty()).map(|f| parse_packet(Some(f))).collect::<Option<Vec<_>>>()?.into_iter().sorted_by(cmp_value).collect_vec();
+
+    let sorted_2_idx = sorted.clone().into_iter().find_position(|f| Some(f) == parse_packet(Some("[[2]]")).as_ref())?.0;
+    let sorted_6_idx = sorted.into_iter().find_position(|f| Some(f) == parse_packet(Some("[[6]]")).as_ref())?.0;
+
+    Some((sorted_2_idx+1)*(sorted_6_idx+1))
+}
+
+/// The packet on a line, or `None` unless it is JSON made of lists and non-negative integers.
+pub fn parse_packet(input: Option<&str>) -> Option<Value> {
+    let packet = serde_json::from_str(input?).ok()?;
+    is_packet(&packet).then_some(packet)
+}
+
+fn is_packet(value: &Value) -> bool {
+    match value {
+        Value::Number(n) => n.is_u64(),
+        Value::Array(l) => l.iter().all(is_packet),
+        _ => false,
+    }
+}
+
+/// `size` pairs of random packets nested up to four lists deep.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{}\n{}", packet(0, rng), packet(0, rng)))
+        .join("\n\n")
+}
+
+fn packet(depth: usize, rng: &mut Rng) -> String {
+    let items = (0..rng.between(0, 4))
+        .map(|_| match depth < 4 && rng.chance(1, 3) {
+            true => packet(depth + 1, rng),
+            false => rng.between(0, 10).to_string(),
+        })
+        .join(",");
+    format!("[{}]", items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 13);
+        assert_eq!(part_one(&input), Some(13));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 13);
+        assert_eq!(part_two(&input), Some(140));
+    }
+
+    #[test]
+    fn test_cmp_value() {
+        let packet = |line| parse_packet(Some(line)).unwrap();
+        assert_eq!(
+            cmp_value(&packet("[[1],4]"), &packet("[1,[4]]")),
+            Ordering::Equal
+        );
+        assert_eq!(cmp_value(&packet("[[]]"), &packet("[]")), Ordering::Greater);
+
+        // any JSON is ordered, and consistently: a total preorder over all these values.
+        let values = r#"null true -1 1.5 "a" "b" {} 0 7 [] [[]] [null] [0,{}] [0,7] [[7],"a"] [7]"#
+            .split(' ')
+            .map(|value| serde_json::from_str::<Value>(value).unwrap())
+            .collect_vec();
+        assert_eq!(cmp_value(&values[0], &values[8]), Ordering::Greater);
+        assert_eq!(cmp_value(&values[12], &values[13]), Ordering::Greater);
+        assert_eq!(cmp_value(&values[4], &values[5]), Ordering::Less);
+        for a in &values {
+            for b in &values {
+                assert_eq!(cmp_value(a, b), cmp_value(b, a).reverse(), "{} {}", a, b);
+                for c in &values {
+                    if cmp_value(a, b).is_le() && cmp_value(b, c).is_le() {
+                        assert!(cmp_value(a, c).is_le(), "{} {} {}", a, b, c);
+                    }
+                }
+            }
+        }
+    }
+}
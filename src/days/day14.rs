@@ -0,0 +1,252 @@
+use std::collections::HashSet;
+
+use crate::helpers::{GetInts, Rng, Simulation};
+use crate::{Param, Params};
+use itertools::Itertools;
+
+pub type Position = (usize, usize);
+
+/// How far below the lowest rock the floor of part two is.
+pub const PARAMS: &[Param] = &[Param::new::<usize>("floor_distance", "2")];
+
+pub fn part_one(input: &str) -> Option<usize> {
+    fill(Cave::new(parse_map(input)?, None)?)
+}
+
+pub fn part_two(input: &str, params: &Params) -> Option<usize> {
+    fill(simulation(input, params, 2)?)
+}
+
+/// Units of sand at rest once the sand stops coming to rest.
+fn fill(mut cave: Cave) -> Option<usize> {
+    loop {
+        match cave.drop_sand() {
+            Sand::Rest(_) => continue,
+            // positions are unsigned, so sand cannot fall left of column 0.
+            Sand::OffMap => return None,
+            Sand::Abyss | Sand::Blocked => return Some(cave.units()),
+        }
+    }
+}
+
+/// Where the last unit of sand went.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sand {
+    Rest(Position),
+    /// Below the lowest rock, with no floor to stop it.
+    Abyss,
+    /// Nowhere, as the source is covered.
+    Blocked,
+    /// Left of column 0.
+    OffMap,
+}
+
+/// The cave filling up with sand, one unit at a time.
+#[derive(Debug, Clone)]
+pub struct Cave {
+    rocks: HashSet<Position>,
+    /// Rocks and sand at rest, the only set sand has to look up as it falls.
+    filled: HashSet<Position>,
+    lowest_rock: usize,
+    /// Depth of the floor, which is infinitely wide, so checked for rather than stored.
+    floor: Option<usize>,
+    pub last: Option<Sand>,
+}
+
+impl Cave {
+    pub fn new(rocks: HashSet<Position>, floor: Option<usize>) -> Option<Self> {
+        Some(Cave {
+            lowest_rock: rocks.iter().map(|rock| rock.1).max()?,
+            filled: rocks.clone(),
+            rocks,
+            floor,
+            last: None,
+        })
+    }
+
+    /// Units of sand at rest.
+    pub fn units(&self) -> usize {
+        self.filled.len() - self.rocks.len()
+    }
+
+    fn is_free(&self, position: Position) -> bool {
+        self.floor.is_none_or(|floor| position.1 < floor) && !self.filled.contains(&position)
+    }
+
+    /// Lets one unit of sand fall from the source until it comes to rest or is lost.
+    pub fn drop_sand(&mut self) -> Sand {
+        let mut sand: Position = (500, 0);
+        if self.filled.contains(&sand) {
+            self.last = Some(Sand::Blocked);
+            return Sand::Blocked;
+        }
+        let outcome = loop {
+            if self.floor.is_none() && sand.1 > self.lowest_rock {
+                break Sand::Abyss;
+            }
+            let below = (sand.0, sand.1 + 1);
+            if self.is_free(below) {
+                sand = below;
+                continue;
+            }
+            let Some(left) = sand.0.checked_sub(1) else {
+                break Sand::OffMap;
+            };
+            if self.is_free((left, sand.1 + 1)) {
+                sand = (left, sand.1 + 1);
+            } else if self.is_free((sand.0 + 1, sand.1 + 1)) {
+                sand = (sand.0 + 1, sand.1 + 1);
+            } else {
+                self.filled.insert(sand);
+                break Sand::Rest(sand);
+            }
+        };
+        self.last = Some(outcome);
+        outcome
+    }
+}
+
+/// The cave of `input` for `part`, to fill one unit of sand at a time.
+pub fn simulation(input: &str, params: &Params, part: u8) -> Option<Cave> {
+    let rocks = parse_map(input)?;
+    let floor = match part {
+        1 => None,
+        _ => Some(
+            rocks.iter().map(|rock| rock.1).max()? + params.get::<usize>(PARAMS, "floor_distance"),
+        ),
+    };
+    Cave::new(rocks, floor)
+}
+
+impl Simulation for Cave {
+    type Snapshot = Cave;
+    const CONDITIONS: &'static str = "`sand x,y`, `units N`";
+
+    fn step(&mut self) -> bool {
+        match self.last {
+            None | Some(Sand::Rest(_)) => {
+                self.drop_sand();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn state(&self) -> String {
+        format!(
+            "{} units of sand at rest, the last one: {:?}",
+            self.units(),
+            self.last
+        )
+    }
+
+    fn render(&self) -> String {
+        let all = || self.filled.iter().chain([&(500, 0)]);
+        let (min_x, max_x) = all().map(|p| p.0).minmax().into_option().unwrap();
+        let max_y = self.floor.unwrap_or(self.lowest_rock);
+        (0..=max_y)
+            .map(|y| {
+                (min_x.saturating_sub(1)..=max_x + 1)
+                    .map(|x| match (x, y) {
+                        position if Some(Sand::Rest(position)) == self.last => '~',
+                        position if self.rocks.contains(&position) => '#',
+                        position if self.filled.contains(&position) => 'o',
+                        (_, y) if Some(y) == self.floor => '#',
+                        (500, 0) => '+',
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+
+    fn snapshot(&self) -> Cave {
+        self.clone()
+    }
+
+    fn restore(&mut self, snapshot: Cave) {
+        *self = snapshot;
+    }
+
+    fn holds(&self, condition: &str) -> Option<bool> {
+        match condition.split_once(' ')? {
+            ("sand", at) => {
+                let [x, y] = at.ints().ok()?;
+                Some(self.last == Some(Sand::Rest((x, y))))
+            }
+            ("units", count) => Some(self.units() >= count.parse().ok()?),
+            _ => None,
+        }
+    }
+}
+
+/// The rock positions, or `None` if a path point is not `x,y`.
+pub fn parse_map(input: &str) -> Option<HashSet<Position>> {
+    let mut map: HashSet<Position> = HashSet::new();
+
+    for path in input.lines() {
+        let path = path
+            .split(" -> ")
+            .map(|c| {
+                c.split(',')
+                    .map(|f| f.parse::<usize>().ok())
+                    .collect::<Option<Vec<_>>>()?
+                    .into_iter()
+                    .collect_tuple::<Position>()
+            })
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .tuple_windows();
+        for (start, end) in path {
+            let x_min = start.0.min(end.0);
+            let x_max = start.0.max(end.0);
+            let y_min = start.1.min(end.1);
+            let y_max = start.1.max(end.1);
+
+            for x in x_min..=x_max {
+                for y in y_min..=y_max {
+                    map.insert((x, y));
+                }
+            }
+        }
+    }
+    Some(map)
+}
+
+/// `size` random rock paths between the sand source and a depth of at most 400.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    let depth = size.clamp(10, 400) as isize;
+    (0..size.max(1))
+        .map(|_| {
+            let mut point = (500 + rng.between(-depth, depth), rng.between(1, depth));
+            let mut path = vec![point];
+            for segment in 0..rng.between(1, 4) {
+                point = match segment % 2 {
+                    0 => (point.0 + rng.between(-8, 8), point.1),
+                    _ => (point.0, (point.1 + rng.between(-8, 8)).clamp(1, depth)),
+                };
+                path.push(point);
+            }
+            path.iter()
+                .map(|(x, y)| format!("{},{}", x, y))
+                .join(" -> ")
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 14);
+        assert_eq!(part_one(&input), Some(24));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 14);
+        assert_eq!(part_two(&input, &Params::default()), Some(93));
+    }
+}
@@ -0,0 +1,296 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+};
+
+use crate::helpers::{GetInts, Overlaps, Rng, Union};
+use crate::{Param, Params};
+use itertools::Itertools;
+
+pub type Position = (isize, isize);
+
+#[derive(Debug)]
+pub struct Square {
+    pub up: Position,
+    pub down: Position,
+    pub center: Position,
+    pub distance: isize,
+}
+
+/// The row part one scans, and the size of the square part two searches.
+pub const PARAMS: &[Param] = &[
+    Param::new::<isize>("row", "2000000"),
+    Param::new::<isize>("limit", "4000000"),
+];
+
+pub fn part_one(input: &str, params: &Params) -> Option<usize> {
+    let row: &isize = &params.get(PARAMS, "row");
+    let mut idx = 0;
+    let mut beacons = HashSet::new();
+    let squares = input
+        .lines()
+        .map(|line| {
+            let [sensor_x, sensor_y, beacon_x, beacon_y]: [isize; 4] = line.ints().ok()?;
+            let sensor = (sensor_x, sensor_y);
+            let beacon = (beacon_x, beacon_y);
+            beacons.insert(beacon);
+            let distance = (sensor.0 - beacon.0).abs() + (sensor.1 - beacon.1).abs();
+
+            idx += 1;
+            Some(Square {
+                up: (sensor.0, sensor.1 - distance),
+                down: (sensor.0, sensor.1 + distance),
+                center: sensor,
+                distance,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let filtered_squares = squares
+        .into_iter()
+        .filter(|square| square.up.1 <= *row && square.down.1 >= *row);
+
+    let mut visited = HashSet::new();
+    for square in filtered_squares {
+        let x_delta = square.distance - (square.center.1 - *row).abs();
+        for x in (square.center.0 - x_delta)..=(square.center.0 + x_delta) {
+            visited.insert(x);
+        }
+    }
+    // a known beacon is not a position where a beacon cannot be.
+    for (x, _) in beacons.into_iter().filter(|beacon| beacon.1 == *row) {
+        visited.remove(&x);
+    }
+    Some(visited.len())
+}
+
+pub fn part_two(input: &str, params: &Params) -> Option<usize> {
+    let limit: &isize = &params.get(PARAMS, "limit");
+    let mut idx = 0;
+    let squares = input
+        .lines()
+        .map(|line| {
+            let [sensor_x, sensor_y, beacon_x, beacon_y]: [isize; 4] = line.ints().ok()?;
+            let sensor = (sensor_x, sensor_y);
+            let beacon = (beacon_x, beacon_y);
+            let distance = (sensor.0 - beacon.0).abs() + (sensor.1 - beacon.1).abs();
+
+            idx += 1;
+            Some(Square {
+                up: (sensor.0, sensor.1 - distance),
+                down: (sensor.0, sensor.1 + distance),
+                center: sensor,
+                distance,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut a = HashMap::<isize, Vec<RangeInclusive<isize>>>::new();
+    for square in squares {
+        let mut i = 0;
+        for row in square.up.1..=square.down.1 {
+            a.entry(row)
+                .and_modify(|r| {
+                    let range = square.up.0 - i..=square.up.0 + i;
+                    if range.overlaps(&(0..=*limit)) {
+                        r.push((square.up.0 - i).max(0)..=(square.up.0 + i).min(*limit));
+                    }
+                })
+                .or_insert_with(|| vec![square.up.0 - i..=square.up.0 + i]);
+            if row < square.center.1 {
+                i += 1;
+            } else {
+                i -= 1;
+            }
+        }
+    }
+
+    let a: HashMap<&isize, Vec<RangeInclusive<isize>>> = a
+        .iter()
+        .map(|r| {
+            let a =
+                r.1.iter()
+                    .cloned()
+                    .sorted_by_key(|ra| *ra.start())
+                    .collect_vec();
+            (r.0, a)
+        })
+        .collect();
+
+    for (row, ranges) in a.into_iter() {
+        if ranges.is_empty() {
+            continue;
+        }
+        let mut a = vec![ranges.first().unwrap().clone()];
+        for range in ranges.into_iter().skip(1) {
+            let b = a.pop().unwrap();
+            if let Some(u) = b.union(&range) {
+                a.push(u);
+            } else {
+                a.push(b);
+                a.push(range);
+            }
+        }
+
+        for window in a.windows(2) {
+            if (window[1].start() - window[0].end()) > 1 {
+                let col = window[0].end() + 1;
+                if row > &0 && row <= limit && col > 0 && col <= *limit {
+                    return Some((col * 4000000 + row) as usize);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Sensors that leave exactly one position of the `limit` square uncovered. Four of them sit
+/// diagonally around that spot, each covering its quadrant right up to it; `size` more are
+/// random and also stop short of it.
+pub fn generate(size: usize, rng: &mut Rng, params: &Params) -> String {
+    let limit: isize = params.get(PARAMS, "limit");
+    let spot = (rng.between(1, limit - 1), rng.between(1, limit - 1));
+    let distance = |a: Position, b: Position| (a.0 - b.0).abs() + (a.1 - b.1).abs();
+
+    // the four sensors around the spot need their full reach, the others less.
+    let mut sensors = [(-1, -1), (1, -1), (-1, 1), (1, 1)]
+        .map(|(dx, dy)| {
+            let width = if dx < 0 { spot.0 } else { limit - spot.0 };
+            let height = if dy < 0 { spot.1 } else { limit - spot.1 };
+            let sensor = (
+                spot.0 + dx * (width / 2 + 1),
+                spot.1 + dy * (height / 2 + 1),
+            );
+            (sensor, distance(sensor, spot) - 1)
+        })
+        .to_vec();
+    while sensors.len() < size + 4 {
+        let sensor = (rng.between(0, limit), rng.between(0, limit));
+        if distance(sensor, spot) > 1 {
+            let range = rng.between(1, distance(sensor, spot) - 1);
+            sensors.push((sensor, range));
+        }
+    }
+    rng.shuffle(&mut sensors);
+
+    sensors
+        .into_iter()
+        .map(|(sensor, range)| {
+            let dx = rng.between(0, range);
+            let beacon = (
+                sensor.0 + if rng.chance(1, 2) { dx } else { -dx },
+                sensor.1
+                    + if rng.chance(1, 2) {
+                        range - dx
+                    } else {
+                        dx - range
+                    },
+            );
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                sensor.0, sensor.1, beacon.0, beacon.1
+            )
+        })
+        .join("\n")
+}
+
+/// Cell-by-cell versions of both parts, to check the solvers against.
+pub mod reference {
+    use std::collections::HashSet;
+
+    use super::PARAMS;
+    use crate::helpers::GetInts;
+    use crate::Params;
+
+    fn sensors(input: &str) -> Vec<([isize; 4], isize)> {
+        input
+            .lines()
+            .map(|line| {
+                let report: [isize; 4] = line.ints().unwrap();
+                let range = (report[0] - report[2]).abs() + (report[1] - report[3]).abs();
+                (report, range)
+            })
+            .collect()
+    }
+
+    fn covered(sensors: &[([isize; 4], isize)], x: isize, y: isize) -> bool {
+        sensors
+            .iter()
+            .any(|([sx, sy, _, _], range)| (sx - x).abs() + (sy - y).abs() <= *range)
+    }
+
+    pub fn part_one(input: &str, params: &Params) -> Option<usize> {
+        let row: isize = params.get(PARAMS, "row");
+        let sensors = sensors(input);
+        let beacons: HashSet<_> = sensors.iter().map(|(s, _)| (s[2], s[3])).collect();
+        let reach = sensors.iter().map(|(_, range)| *range).max()?;
+        let min_x = sensors.iter().map(|(s, _)| s[0]).min()? - reach;
+        let max_x = sensors.iter().map(|(s, _)| s[0]).max()? + reach;
+        Some(
+            (min_x..=max_x)
+                .filter(|x| covered(&sensors, *x, row) && !beacons.contains(&(*x, row)))
+                .count(),
+        )
+    }
+
+    pub fn part_two(input: &str, params: &Params) -> Option<usize> {
+        let limit: isize = params.get(PARAMS, "limit");
+        let sensors = sensors(input);
+        let spots = (0..=limit)
+            .flat_map(|y| (0..=limit).map(move |x| (x, y)))
+            .filter(|(x, y)| !covered(&sensors, *x, *y))
+            .take(2)
+            .collect::<Vec<_>>();
+        // the puzzle promises a single spot; anything else is not a valid input.
+        let [(x, y)] = spots[..] else {
+            panic!("expected exactly one uncovered position, found {:?}", spots);
+        };
+        Some((x * 4000000 + y) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 15);
+        assert_eq!(
+            part_one(&input, &Params::default().with("row", 10)),
+            Some(26)
+        );
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 15);
+        assert_eq!(
+            part_two(&input, &Params::default().with("limit", 20)),
+            Some(56000011)
+        );
+    }
+
+    #[test]
+    fn test_reference() {
+        let params = Params::default().with("limit", 20).with("row", 10);
+        let generate = |rng: &mut Rng| {
+            let size = rng.below(8);
+            generate(size, rng, &params)
+        };
+        crate::differential::check(
+            15,
+            200,
+            generate,
+            |input| part_one(input, &params),
+            |input| reference::part_one(input, &params),
+        );
+        crate::differential::check(
+            15,
+            200,
+            generate,
+            |input| part_two(input, &params),
+            |input| reference::part_two(input, &params),
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+
+use crate::helpers::{GetInts, Rng};
+use crate::{Param, Params};
+use itertools::Itertools;
+
+/// Valve graph reduced to the valves worth opening, plus the starting valve.
+#[derive(Debug)]
+pub struct Network {
+    pub flows: Vec<usize>,
+    /// `distances[a][b]` is the travel time between compressed valves `a` and `b`.
+    pub distances: Vec<Vec<usize>>,
+    /// Travel time from the starting valve to each compressed valve.
+    pub from_start: Vec<usize>,
+}
+
+/// Most working valves the search can track, one bit each, with a table entry per subset.
+/// Sharing the valves between agents goes through every subset of every subset, 3^n steps per
+/// agent after the first: about 43 million at this limit, while puzzle inputs have 15.
+pub const MAX_USEFUL_VALVES: usize = 16;
+
+/// The compressed network, or `None` if a report is malformed, a tunnel leads to an unknown
+/// valve, there is no valve `AA` or too many valves work.
+pub fn parse_input(input: &str) -> Option<Network> {
+    let valves = input
+        .lines()
+        .map(|line| {
+            let words = line.split_whitespace().collect_vec();
+            let [flow]: [usize; 1] = line.ints().ok()?;
+            let tunnels = words
+                .get(9..)?
+                .iter()
+                .map(|tunnel| tunnel.trim_end_matches(','))
+                .collect_vec();
+            Some((words[1], flow, tunnels))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let ids: HashMap<&str, usize> = valves
+        .iter()
+        .enumerate()
+        .map(|(idx, (name, _, _))| (*name, idx))
+        .collect();
+
+    // Floyd–Warshall over the full graph.
+    let mut distances = vec![vec![usize::MAX / 2; valves.len()]; valves.len()];
+    for (idx, (_, _, tunnels)) in valves.iter().enumerate() {
+        distances[idx][idx] = 0;
+        for tunnel in tunnels {
+            distances[idx][*ids.get(tunnel)?] = 1;
+        }
+    }
+    for k in 0..valves.len() {
+        for i in 0..valves.len() {
+            for j in 0..valves.len() {
+                distances[i][j] = distances[i][j].min(distances[i][k] + distances[k][j]);
+            }
+        }
+    }
+
+    let useful = (0..valves.len())
+        .filter(|idx| valves[*idx].1 > 0)
+        .collect_vec();
+    let start = *ids.get("AA")?;
+    if useful.len() > MAX_USEFUL_VALVES {
+        return None;
+    }
+    Some(Network {
+        flows: useful.iter().map(|idx| valves[*idx].1).collect(),
+        distances: useful
+            .iter()
+            .map(|a| useful.iter().map(|b| distances[*a][*b]).collect())
+            .collect(),
+        from_start: useful.iter().map(|idx| distances[start][*idx]).collect(),
+    })
+}
+
+/// Records, for every set of opened valves, the most pressure one agent can release by
+/// opening exactly that set within the remaining time. Paths that reach the same valve with the
+/// same valves open and time left are only followed from the one that released the most.
+fn search(
+    network: &Network,
+    valve: usize,
+    minutes_left: usize,
+    opened: usize,
+    released: usize,
+    seen: &mut HashMap<(usize, usize, usize), usize>,
+    best: &mut [usize],
+) {
+    let state = (valve, minutes_left, opened);
+    if seen.get(&state).is_some_and(|&most| most >= released) {
+        return;
+    }
+    seen.insert(state, released);
+    best[opened] = best[opened].max(released);
+    for next in 0..network.flows.len() {
+        let cost = network.distances[valve][next] + 1;
+        if opened & (1 << next) != 0 || cost >= minutes_left {
+            continue;
+        }
+        let minutes_left = minutes_left - cost;
+        search(
+            network,
+            next,
+            minutes_left,
+            opened | (1 << next),
+            released + minutes_left * network.flows[next],
+            seen,
+            best,
+        );
+    }
+}
+
+/// The most pressure `agents` can release together in `minutes`, or `None` without any agent.
+pub fn max_pressure(input: &str, minutes: usize, agents: usize) -> Option<usize> {
+    if agents == 0 {
+        return None;
+    }
+    let network = parse_input(input)?;
+    let valves = network.flows.len();
+    let full = (1 << valves) - 1;
+
+    let mut best = vec![0; 1 << valves];
+    let mut seen = HashMap::new();
+    for (first, distance) in network.from_start.iter().enumerate() {
+        if distance + 1 < minutes {
+            let minutes_left = minutes - distance - 1;
+            let released = minutes_left * network.flows[first];
+            search(
+                &network,
+                first,
+                minutes_left,
+                1 << first,
+                released,
+                &mut seen,
+                &mut best,
+            );
+        }
+    }
+
+    // best pressure when opening any subset of each mask.
+    for valve in 0..valves {
+        for mask in 0..=full {
+            if mask & (1 << valve) != 0 {
+                best[mask] = best[mask].max(best[mask ^ (1 << valve)]);
+            }
+        }
+    }
+
+    // each other agent takes a disjoint subset of the valves left by the others, until one more
+    // agent finds nothing left to gain, as happens at the latest once each opens a single valve.
+    let mut combined = best.clone();
+    for _ in 1..agents {
+        let next: Vec<_> = (0..=full)
+            .map(|mask| {
+                let mut subset = mask;
+                let mut result = combined[mask];
+                while subset > 0 {
+                    result = result.max(best[subset] + combined[mask ^ subset]);
+                    subset = (subset - 1) & mask;
+                }
+                result
+            })
+            .collect();
+        if next == combined {
+            break;
+        }
+        combined = next;
+    }
+    combined.get(full).copied()
+}
+
+/// The time and agents of each part.
+pub const PARAMS: &[Param] = &[
+    Param::new::<usize>("minutes_one", "30"),
+    Param::new::<usize>("agents_one", "1"),
+    Param::new::<usize>("minutes_two", "26"),
+    Param::new::<usize>("agents_two", "2"),
+];
+
+pub fn part_one(input: &str, params: &Params) -> Option<usize> {
+    let minutes = params.get(PARAMS, "minutes_one");
+    max_pressure(input, minutes, params.get(PARAMS, "agents_one"))
+}
+
+pub fn part_two(input: &str, params: &Params) -> Option<usize> {
+    let minutes = params.get(PARAMS, "minutes_two");
+    max_pressure(input, minutes, params.get(PARAMS, "agents_two"))
+}
+
+/// `size` valves (at least two) joined by a random connected network of tunnels, with a working
+/// flow in at most fifteen of them.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    let count = size.clamp(2, 26 * 26);
+    let mut names = ('A'..='Z')
+        .cartesian_product('A'..='Z')
+        .map(|(a, b)| format!("{}{}", a, b))
+        .filter(|name| name != "AA")
+        .collect_vec();
+    rng.shuffle(&mut names);
+    names.truncate(count - 1);
+    names.insert(0, "AA".to_string());
+
+    let mut tunnels = vec![vec![]; count];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for idx in 1..count {
+        connect(idx, rng.below(idx));
+    }
+    for _ in 0..count / 2 {
+        connect(rng.below(count), rng.below(count));
+    }
+
+    let mut flows = vec![0; count];
+    let mut working = (1..count).collect_vec();
+    rng.shuffle(&mut working);
+    for idx in working.into_iter().take(15.min(count.div_ceil(2))) {
+        flows[idx] = rng.between(1, 25);
+    }
+
+    (0..count)
+        .map(|idx| {
+            let targets = tunnels[idx].iter().map(|target| &names[*target]).join(", ");
+            match tunnels[idx].len() {
+                1 => format!(
+                    "Valve {} has flow rate={}; tunnel leads to valve {}",
+                    names[idx], flows[idx], targets
+                ),
+                _ => format!(
+                    "Valve {} has flow rate={}; tunnels lead to valves {}",
+                    names[idx], flows[idx], targets
+                ),
+            }
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 16);
+        assert_eq!(part_one(&input, &Params::default()), Some(1651));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 16);
+        assert_eq!(part_two(&input, &Params::default()), Some(1707));
+    }
+
+    #[test]
+    fn test_agents() {
+        let input = crate::read_file("examples", 16);
+        assert_eq!(max_pressure(&input, 26, 1), Some(1327));
+        assert_eq!(max_pressure(&input, 26, 0), None);
+        assert_eq!(max_pressure(&input, 26, 3), Some(1794));
+        // with an agent for each of the six working valves, each goes straight to its own.
+        assert_eq!(max_pressure(&input, 26, 6), Some(1830));
+        assert_eq!(max_pressure(&input, 26, 1000), Some(1830));
+        assert_eq!(max_pressure(&input, 1, 2), Some(0));
+
+        // one more working valve than the search tracks.
+        let too_many = (0..=MAX_USEFUL_VALVES)
+            .map(|idx| {
+                let next = (idx + 1) % (MAX_USEFUL_VALVES + 1);
+                format!(
+                    "Valve V{} has flow rate=1; tunnel leads to valve V{}",
+                    idx, next
+                )
+            })
+            .join("\n");
+        let input = format!(
+            "Valve AA has flow rate=0; tunnel leads to valve V0\n{}",
+            too_many
+        );
+        assert_eq!(max_pressure(&input, 30, 1), None);
+    }
+}
@@ -0,0 +1,564 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::checkpoint::{self, Checkpoint};
+use crate::helpers::{GetInts, Rng, Simulation};
+use crate::{Param, Params};
+use itertools::Itertools;
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rock {
+    Minus,
+    Plus,
+    L,
+    I,
+    Block,
+}
+
+pub type Position = (usize, usize);
+
+#[derive(Debug, Clone, Copy)]
+pub struct RockPosition {
+    pub rock: Rock,
+    pub position: Position,
+}
+
+impl RockPosition {
+    pub fn initial_position(rock: &Rock, highest_rock: &Option<isize>) -> Self {
+        let highest_rock = highest_rock.unwrap_or(-1);
+        Self {
+            rock: *rock,
+            position: match rock {
+                Rock::Minus => (2, (highest_rock + 4) as usize),
+                Rock::Plus => (3, (highest_rock + 5) as usize),
+                Rock::L => (4, (highest_rock + 4) as usize),
+                Rock::I => (2, (highest_rock + 4) as usize),
+                Rock::Block => (2, (highest_rock + 4) as usize),
+            },
+        }
+    }
+    pub fn move_down(&self, map: &HashSet<Position>) -> Option<Self> {
+        let can_move = !self.positions().iter().any(|position| position.1 == 0);
+
+        if !can_move {
+            return None;
+        }
+
+        let new_position = Self {
+            rock: self.rock,
+            position: (self.position.0, self.position.1 - 1),
+        };
+
+        let new_position_blocked = new_position
+            .positions()
+            .iter()
+            .any(|position| map.contains(position));
+
+        match new_position_blocked {
+            true => None,
+            false => Some(new_position),
+        }
+    }
+
+    pub fn move_side(&self, jet: &char, map: &HashSet<Position>, width: usize) -> Self {
+        let can_move = match jet {
+            '<' => !self.positions().iter().any(|position| position.0 == 0),
+            '>' => !self
+                .positions()
+                .iter()
+                .any(|position| position.0 == width - 1),
+            _ => unimplemented!(),
+        };
+
+        let delta = match jet {
+            '<' => -1,
+            '>' => 1,
+            _ => unimplemented!(),
+        };
+
+        let new_position = match can_move {
+            true => Self {
+                rock: self.rock,
+                position: ((self.position.0 as isize + delta) as usize, self.position.1),
+            },
+            false => *self,
+        };
+
+        let new_position_blocked = new_position
+            .positions()
+            .iter()
+            .any(|position| map.contains(position));
+
+        match new_position_blocked {
+            true => *self,
+            false => new_position,
+        }
+    }
+
+    pub fn positions(&self) -> Vec<Position> {
+        match self.rock {
+            Rock::Minus => Vec::from([
+                self.position,
+                (self.position.0 + 1, self.position.1),
+                (self.position.0 + 2, self.position.1),
+                (self.position.0 + 3, self.position.1),
+            ]),
+            Rock::Plus => Vec::from([
+                self.position,
+                (self.position.0 - 1, self.position.1),
+                (self.position.0 + 1, self.position.1),
+                (self.position.0, self.position.1 - 1),
+                (self.position.0, self.position.1 + 1),
+            ]),
+            Rock::L => Vec::from([
+                self.position,
+                (self.position.0, self.position.1 + 1),
+                (self.position.0, self.position.1 + 2),
+                (self.position.0 - 1, self.position.1),
+                (self.position.0 - 2, self.position.1),
+            ]),
+            Rock::I => Vec::from([
+                self.position,
+                (self.position.0, self.position.1 + 1),
+                (self.position.0, self.position.1 + 2),
+                (self.position.0, self.position.1 + 3),
+            ]),
+            Rock::Block => Vec::from([
+                self.position,
+                (self.position.0 + 1, self.position.1),
+                (self.position.0, self.position.1 + 1),
+                (self.position.0 + 1, self.position.1 + 1),
+            ]),
+        }
+    }
+}
+
+pub fn part_one(input: &str, params: &Params) -> Option<isize> {
+    let mut tower = simulation(input, params, 1)?;
+    checkpoint::run(&mut tower, 17, 1, params, usize::MAX);
+    Some(tower.chamber.height())
+}
+
+pub fn part_two(input: &str, params: &Params) -> Option<isize> {
+    let mut tower = simulation(input, params, 2)?;
+    checkpoint::run(&mut tower, 17, 2, params, usize::MAX);
+    Some(tower.chamber.height())
+}
+
+/// The order the rocks fall in, over and over.
+const ROCK_ORDER: [Rock; 5] = [Rock::Minus, Rock::Plus, Rock::L, Rock::I, Rock::Block];
+
+/// The chamber rocks fall into, one at a time, pushed around by the jets.
+#[derive(Debug, Clone)]
+pub struct Chamber {
+    pub map: HashSet<Position>,
+    pub highest_rock: Option<isize>,
+    jets: Vec<char>,
+    next_jet: usize,
+    width: usize,
+    /// Rocks at rest, and where the last one came to rest.
+    pub rocks: usize,
+    pub last: Option<RockPosition>,
+}
+
+impl Chamber {
+    pub fn new(jets: Vec<char>, width: usize) -> Self {
+        Chamber {
+            map: HashSet::new(),
+            highest_rock: None,
+            jets,
+            next_jet: 0,
+            width,
+            rocks: 0,
+            last: None,
+        }
+    }
+
+    pub fn height(&self) -> isize {
+        self.highest_rock.map_or(0, |highest_rock| highest_rock + 1)
+    }
+
+    pub fn next_rock(&self) -> Rock {
+        ROCK_ORDER[self.rocks % ROCK_ORDER.len()]
+    }
+
+    /// Lets the next rock fall until it comes to rest.
+    pub fn drop_rock(&mut self) -> RockPosition {
+        let rock = self.next_rock();
+        let mut position = RockPosition::initial_position(&rock, &self.highest_rock);
+        loop {
+            let jet = self.jets[self.next_jet];
+            self.next_jet = (self.next_jet + 1) % self.jets.len();
+            let side_position = position.move_side(&jet, &self.map, self.width);
+
+            if let Some(down_position) = side_position.move_down(&self.map) {
+                position = down_position
+            } else {
+                position = side_position;
+                break;
+            }
+        }
+        for p in position.positions() {
+            self.highest_rock = Some(match self.highest_rock {
+                None => p.1 as isize,
+                Some(h) => h.max(p.1 as isize),
+            });
+            self.map.insert(p);
+        }
+        self.rocks += 1;
+        self.last = Some(position);
+        position
+    }
+
+    /// The top 51 rows of the tower, a column per line.
+    fn top(&self) -> String {
+        let Some(highest_rock) = self.highest_rock else {
+            return String::new();
+        };
+        // TODO: Maintain a list of highest positions for each col, instead of computing this
+        (0..self.width)
+            .map(|x| {
+                ((highest_rock - 50)..=highest_rock)
+                    .rev()
+                    .map(|y| match self.map.get(&(x, y as usize)) {
+                        Some(_) => '#',
+                        None => '.',
+                    })
+                    .join("")
+            })
+            .join("\n")
+    }
+}
+
+/// The next rock, the next jet and the top of the tower, which decide everything that follows.
+type TowerState = (Rock, usize, String);
+
+/// A tower growing to `rocks` rocks. With `skip_cycles`, once its top repeats, every whole
+/// repetition that still fits is added at once rather than rock by rock.
+#[derive(Debug, Clone)]
+pub struct Tower {
+    pub chamber: Chamber,
+    rocks: usize,
+    skip_cycles: bool,
+    /// Rocks fallen so far, skipped ones included.
+    pub fallen: usize,
+    visited: HashMap<TowerState, (Option<isize>, usize)>,
+    found_cycle: bool,
+}
+
+impl Tower {
+    pub fn new(chamber: Chamber, rocks: usize, skip_cycles: bool) -> Self {
+        Tower {
+            chamber,
+            rocks,
+            skip_cycles,
+            fallen: 0,
+            visited: HashMap::new(),
+            found_cycle: false,
+        }
+    }
+
+    fn skip_cycle(&mut self) {
+        let highest_rock = self.chamber.highest_rock;
+        let state = (
+            self.chamber.next_rock(),
+            self.chamber.next_jet,
+            self.chamber.top(),
+        );
+
+        if let Some((high, idx)) = self.visited.get(&state) {
+            let cycle_duration = self.fallen - idx;
+            let cycle_height_increase = highest_rock.unwrap() - high.unwrap();
+
+            let remaining_rocks = self.rocks - self.fallen;
+            let cycles_remaining = remaining_rocks / cycle_duration;
+
+            let rock_increment = cycles_remaining as isize * cycle_height_increase;
+
+            self.fallen += cycle_duration * cycles_remaining;
+
+            self.found_cycle = true;
+
+            // copy the whole window the state was keyed on, overhangs included.
+            for x in 0..self.chamber.width {
+                for y in ((highest_rock.unwrap() - 50).max(0)..=highest_rock.unwrap()).rev() {
+                    if self.chamber.map.contains(&(x, y as usize)) {
+                        self.chamber.map.insert((x, (y + rock_increment) as usize));
+                    }
+                }
+            }
+            self.chamber.highest_rock = Some(highest_rock.unwrap() + rock_increment);
+        } else if self.fallen > 1000 {
+            self.visited.insert(state, (highest_rock, self.fallen));
+        }
+    }
+}
+
+/// The width of the chamber, and how many rocks fall in each part.
+pub const PARAMS: &[Param] = &[
+    Param::new::<usize>("width", "7"),
+    Param::new::<usize>("rocks_one", "2022"),
+    Param::new::<usize>("rocks_two", "1000000000000"),
+    checkpoint::EVERY,
+    checkpoint::PATH,
+    checkpoint::RESUME,
+];
+
+/// The tower of `input` for `part`, which skips ahead once it finds a cycle.
+pub fn simulation(input: &str, params: &Params, part: u8) -> Option<Tower> {
+    let chamber = Chamber::new(parse_jets(input)?, params.get(PARAMS, "width"));
+    Some(match part {
+        1 => Tower::new(chamber, params.get(PARAMS, "rocks_one"), false),
+        _ => Tower::new(chamber, params.get(PARAMS, "rocks_two"), true),
+    })
+}
+
+/// Rows of the chamber shown below its top.
+const RENDERED_ROWS: isize = 20;
+
+impl Simulation for Tower {
+    type Snapshot = (Chamber, usize, bool);
+    const CONDITIONS: &'static str = "`height N`, `rock x,y`";
+
+    fn step(&mut self) -> bool {
+        if self.fallen >= self.rocks {
+            return false;
+        }
+        if self.skip_cycles && !self.found_cycle {
+            self.skip_cycle();
+        }
+        // the skipped cycles can make up every remaining rock.
+        if self.fallen < self.rocks {
+            self.chamber.drop_rock();
+            self.fallen += 1;
+        }
+        true
+    }
+
+    fn state(&self) -> String {
+        format!(
+            "{} rocks fallen ({} skipped), {} units tall, the last one: {:?}",
+            self.fallen,
+            self.fallen - self.chamber.rocks,
+            self.chamber.height(),
+            self.chamber.last
+        )
+    }
+
+    fn render(&self) -> String {
+        let chamber = &self.chamber;
+        let last = chamber
+            .last
+            .map(|rock| rock.positions())
+            .unwrap_or_default();
+        let rows = (0..chamber.height())
+            .rev()
+            .take(RENDERED_ROWS as usize)
+            .map(|y| {
+                let row = (0..chamber.width)
+                    .map(|x| match (x, y as usize) {
+                        position if last.contains(&position) => '@',
+                        position if chamber.map.contains(&position) => '#',
+                        _ => '.',
+                    })
+                    .collect::<String>();
+                format!("|{}|", row)
+            });
+        let bottom = match chamber.height() > RENDERED_ROWS {
+            true => format!("|{}|", "~".repeat(chamber.width)),
+            false => format!("+{}+", "-".repeat(chamber.width)),
+        };
+        rows.chain([bottom]).join("\n")
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        (self.chamber.clone(), self.fallen, self.found_cycle)
+    }
+
+    fn restore(&mut self, (chamber, fallen, found_cycle): Self::Snapshot) {
+        self.chamber = chamber;
+        self.fallen = fallen;
+        self.found_cycle = found_cycle;
+        // states are only ever added, so those seen since the snapshot are the ones to forget.
+        self.visited.retain(|_, (_, idx)| *idx < fallen);
+    }
+
+    fn holds(&self, condition: &str) -> Option<bool> {
+        match condition.split_once(' ')? {
+            ("height", height) => Some(self.chamber.height() >= height.parse().ok()?),
+            ("rock", at) => {
+                let [x, y] = at.ints().ok()?;
+                let last = self.chamber.last.map(|rock| rock.positions());
+                Some(last.unwrap_or_default().contains(&(x, y)))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Checkpoint for Tower {
+    fn save(&self) -> Value {
+        let chamber = &self.chamber;
+        let visited = self
+            .visited
+            .iter()
+            .map(|((rock, jet, top), (high, idx))| {
+                let rock = ROCK_ORDER.iter().position(|other| other == rock);
+                (rock, jet, top, high, idx)
+            })
+            .collect_vec();
+        json!({
+            "map": chamber.map,
+            "highest_rock": chamber.highest_rock,
+            "next_jet": chamber.next_jet,
+            "rocks": chamber.rocks,
+            "fallen": self.fallen,
+            "visited": visited,
+            "found_cycle": self.found_cycle,
+        })
+    }
+
+    fn load(&mut self, state: &Value) -> Option<()> {
+        let field = |name: &str| state.get(name).cloned();
+        let visited: Vec<(usize, usize, String, Option<isize>, usize)> =
+            serde_json::from_value(field("visited")?).ok()?;
+        let next_jet = serde_json::from_value(field("next_jet")?).ok()?;
+        if next_jet >= self.chamber.jets.len() {
+            return None;
+        }
+
+        self.chamber.map = serde_json::from_value(field("map")?).ok()?;
+        self.chamber.highest_rock = serde_json::from_value(field("highest_rock")?).ok()?;
+        self.chamber.next_jet = next_jet;
+        self.chamber.rocks = serde_json::from_value(field("rocks")?).ok()?;
+        self.chamber.last = None;
+        self.fallen = serde_json::from_value(field("fallen")?).ok()?;
+        self.visited = visited
+            .into_iter()
+            .map(|(rock, jet, top, high, idx)| {
+                Some(((*ROCK_ORDER.get(rock)?, jet, top), (high, idx)))
+            })
+            .collect::<Option<_>>()?;
+        self.found_cycle = serde_json::from_value(field("found_cycle")?).ok()?;
+        Some(())
+    }
+}
+
+/// The jet pattern, or `None` unless it is a non-empty run of `<` and `>`.
+pub fn parse_jets(input: &str) -> Option<Vec<char>> {
+    let jets = input.trim_end().chars().collect::<Vec<_>>();
+    match !jets.is_empty() && jets.iter().all(|jet| matches!(jet, '<' | '>')) {
+        true => Some(jets),
+        false => None,
+    }
+}
+
+/// A jet pattern of `size` random pushes.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    (0..size.max(1))
+        .map(|_| if rng.chance(1, 2) { '<' } else { '>' })
+        .collect()
+}
+
+/// A plain rock-by-rock simulation, without cycle detection, to check the solvers against.
+pub mod reference {
+    use std::collections::HashSet;
+
+    use super::PARAMS;
+    use crate::Params;
+
+    /// Cells of each rock, as offsets from its bottom-left corner.
+    const ROCKS: [&[(usize, usize)]; 5] = [
+        &[(0, 0), (1, 0), (2, 0), (3, 0)],
+        &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+        &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+        &[(0, 0), (0, 1), (0, 2), (0, 3)],
+        &[(0, 0), (1, 0), (0, 1), (1, 1)],
+    ];
+
+    fn height(input: &str, width: usize, rocks: usize) -> usize {
+        let jets = input.trim().chars().collect::<Vec<_>>();
+        let mut filled = HashSet::new();
+        let mut height = 0;
+        let mut jet = 0;
+        for rock in ROCKS.iter().cycle().take(rocks) {
+            let fits = |x: isize, y: isize| {
+                rock.iter().all(|(dx, dy)| {
+                    let (x, y) = (x + *dx as isize, y + *dy as isize);
+                    x >= 0 && (x as usize) < width && y >= 0 && !filled.contains(&(x, y))
+                })
+            };
+            let (mut x, mut y) = (2, height as isize + 3);
+            loop {
+                let push = if jets[jet % jets.len()] == '<' { -1 } else { 1 };
+                jet += 1;
+                if fits(x + push, y) {
+                    x += push;
+                }
+                if !fits(x, y - 1) {
+                    break;
+                }
+                y -= 1;
+            }
+            for (dx, dy) in rock.iter() {
+                let cell = (x + *dx as isize, y + *dy as isize);
+                height = height.max(cell.1 as usize + 1);
+                filled.insert(cell);
+            }
+        }
+        height
+    }
+
+    pub fn part_one(input: &str, params: &Params) -> Option<isize> {
+        let rocks = params.get(PARAMS, "rocks_one");
+        Some(height(input, params.get(PARAMS, "width"), rocks) as isize)
+    }
+
+    pub fn part_two(input: &str, params: &Params) -> Option<isize> {
+        let rocks = params.get(PARAMS, "rocks_two");
+        Some(height(input, params.get(PARAMS, "width"), rocks) as isize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 17);
+        assert_eq!(part_one(&input, &Params::default()), Some(3068));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 17);
+        assert_eq!(part_two(&input, &Params::default()), Some(1514285714288));
+    }
+
+    #[test]
+    fn test_reference() {
+        // enough rocks for part two to splice in a cycle, few enough for the reference.
+        let params = Params::default()
+            .with("rocks_one", 3000)
+            .with("rocks_two", 3000);
+        let generate = |rng: &mut Rng| {
+            let size = 1 + rng.below(40);
+            generate(size, rng, &params)
+        };
+        crate::differential::check(
+            17,
+            50,
+            generate,
+            |input| part_one(input, &params),
+            |input| reference::part_one(input, &params),
+        );
+        crate::differential::check(
+            17,
+            50,
+            generate,
+            |input| part_two(input, &params),
+            |input| reference::part_two(input, &params),
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::helpers::{GetInts, Rng};
+use crate::Params;
+use itertools::Itertools;
+
+pub type Position = (isize, isize, isize);
+
+trait AddXYZ {
+    fn add_x(&self, x: &isize) -> Position;
+    fn add_y(&self, y: &isize) -> Position;
+    fn add_z(&self, z: &isize) -> Position;
+}
+
+impl AddXYZ for Position {
+    fn add_x(&self, x: &isize) -> Position {
+        (self.0 + x, self.1, self.2)
+    }
+
+    fn add_y(&self, y: &isize) -> Position {
+        (self.0, self.1 + y, self.2)
+    }
+
+    fn add_z(&self, z: &isize) -> Position {
+        (self.0, self.1, self.2 + z)
+    }
+}
+
+trait Neighbours {
+    fn neighbours(&self) -> Vec<Position>;
+}
+
+impl Neighbours for Position {
+    fn neighbours(&self) -> Vec<Self> {
+        let deltas = vec![
+            (1, 0, 0),
+            (0, 1, 0),
+            (-1, 0, 0),
+            (0, -1, 0),
+            (0, 0, 1),
+            (0, 0, -1),
+        ];
+        deltas
+            .into_iter()
+            .map(|delta| (delta.0 + self.0, delta.1 + self.1, delta.2 + self.2))
+            .collect_vec()
+    }
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let coordinates = parse_cubes(input)?;
+
+    let mut sides = HashMap::new();
+    for coordinate in coordinates {
+        // Front
+        sides
+            .entry((
+                coordinate,
+                coordinate.add_x(&1),
+                coordinate.add_z(&1),
+                coordinate.add_x(&1).add_z(&1),
+            ))
+            .and_modify(|f| *f += 1)
+            .or_insert(1);
+        // Back
+        sides
+            .entry((
+                coordinate.add_y(&1),
+                coordinate.add_y(&1).add_x(&1),
+                coordinate.add_y(&1).add_z(&1),
+                coordinate.add_y(&1).add_x(&1).add_z(&1),
+            ))
+            .and_modify(|f| *f += 1)
+            .or_insert(1);
+        // Bottom
+        sides
+            .entry((
+                coordinate,
+                coordinate.add_x(&1),
+                coordinate.add_y(&1),
+                coordinate.add_x(&1).add_y(&1),
+            ))
+            .and_modify(|f| *f += 1)
+            .or_insert(1);
+        // Top
+        sides
+            .entry((
+                coordinate.add_z(&1),
+                coordinate.add_z(&1).add_x(&1),
+                coordinate.add_z(&1).add_y(&1),
+                coordinate.add_z(&1).add_x(&1).add_y(&1),
+            ))
+            .and_modify(|f| *f += 1)
+            .or_insert(1);
+        // Left
+        sides
+            .entry((
+                coordinate,
+                coordinate.add_z(&1),
+                coordinate.add_y(&1),
+                coordinate.add_z(&1).add_y(&1),
+            ))
+            .and_modify(|f| *f += 1)
+            .or_insert(1);
+        // Right
+        sides
+            .entry((
+                coordinate.add_x(&1),
+                coordinate.add_x(&1).add_z(&1),
+                coordinate.add_x(&1).add_y(&1),
+                coordinate.add_x(&1).add_z(&1).add_y(&1),
+            ))
+            .and_modify(|f| *f += 1)
+            .or_insert(1);
+    }
+
+    Some(sides.into_iter().filter(|(_, v)| v == &1).count())
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let coordinates = parse_cubes(input)?;
+    let (min_x, max_x) = coordinates.iter().map(|c| c.0).minmax().into_option()?;
+    let (min_y, max_y) = coordinates.iter().map(|c| c.1).minmax().into_option()?;
+    let (min_z, max_z) = coordinates.iter().map(|c| c.2).minmax().into_option()?;
+
+    let (min_x, max_x) = (min_x - 1, max_x + 1);
+    let (min_y, max_y) = (min_y - 1, max_y + 1);
+    let (min_z, max_z) = (min_z - 1, max_z + 1);
+
+    let mut map = HashMap::new();
+    map.insert((min_x, min_y, min_z), '-');
+    for coordinate in coordinates.clone() {
+        map.insert(coordinate, '#');
+    }
+    for z in min_z..=max_z {
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let c = map.get(&(x, y, z));
+                if c.is_some() {
+                    continue;
+                }
+                let any_outside = (x, y, z)
+                    .neighbours()
+                    .iter()
+                    .filter_map(|m| map.get(m))
+                    .any(|m| m == &'-');
+                if any_outside {
+                    map.insert((x, y, z), '-');
+                }
+            }
+        }
+    }
+
+    for z in (min_z..=max_z).rev() {
+        for y in (min_y..=max_y).rev() {
+            for x in (min_x..=max_x).rev() {
+                let c = map.get(&(x, y, z));
+                if c.is_some() {
+                    continue;
+                }
+                let any_outside = (x, y, z)
+                    .neighbours()
+                    .iter()
+                    .filter_map(|m| map.get(m))
+                    .any(|m| m == &'-');
+                if any_outside {
+                    map.insert((x, y, z), '-');
+                }
+            }
+        }
+    }
+
+    let mut a = 0;
+    for coordinate in &coordinates {
+        a += coordinate
+            .neighbours()
+            .iter()
+            .filter_map(|n| map.get(n))
+            .filter(|c| *c == &'-')
+            .count();
+    }
+
+    Some(a)
+}
+
+/// The droplet's cubes, or `None` unless every line holds three coordinates.
+pub fn parse_cubes(input: &str) -> Option<Vec<Position>> {
+    input
+        .lines()
+        .map(|line| {
+            let [x, y, z] = line.ints().ok()?;
+            Some((x, y, z))
+        })
+        .collect()
+}
+
+/// `size` distinct cubes (at least two) packed into a box barely twice their volume, so some
+/// air ends up trapped.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    let count = size.max(2);
+    let side = ((2 * count) as f64).cbrt().ceil() as isize + 1;
+    loop {
+        let mut seen = HashSet::new();
+        let mut cubes = vec![];
+        while cubes.len() < count {
+            let cube = [(); 3].map(|_| rng.between(0, side - 1));
+            if seen.insert(cube) {
+                cubes.push(cube);
+            }
+        }
+        // the droplet must span more than one plane along every axis.
+        if (0..3).all(|axis| cubes.iter().map(|cube| cube[axis]).unique().count() > 1) {
+            return cubes.iter().map(|cube| cube.iter().join(",")).join("\n");
+        }
+    }
+}
+
+/// Neighbour counting and a flood fill from outside the droplet, to check the solvers against.
+pub mod reference {
+    use std::collections::HashSet;
+
+    use super::{Neighbours, Position};
+    use crate::helpers::GetInts;
+
+    fn cubes(input: &str) -> HashSet<Position> {
+        input
+            .lines()
+            .map(|line| {
+                let [x, y, z] = line.ints().unwrap();
+                (x, y, z)
+            })
+            .collect()
+    }
+
+    pub fn part_one(input: &str) -> Option<usize> {
+        let cubes = cubes(input);
+        Some(
+            cubes
+                .iter()
+                .flat_map(|cube| cube.neighbours())
+                .filter(|neighbour| !cubes.contains(neighbour))
+                .count(),
+        )
+    }
+
+    pub fn part_two(input: &str) -> Option<usize> {
+        let cubes = cubes(input);
+        let low = cubes.iter().map(|c| c.0.min(c.1).min(c.2)).min()? - 1;
+        let high = cubes.iter().map(|c| c.0.max(c.1).max(c.2)).max()? + 1;
+        let inside = |c: &Position| [c.0, c.1, c.2].iter().all(|v| (low..=high).contains(v));
+
+        let mut outside = HashSet::from([(low, low, low)]);
+        let mut queue = vec![(low, low, low)];
+        while let Some(position) = queue.pop() {
+            for neighbour in position.neighbours() {
+                if inside(&neighbour) && !cubes.contains(&neighbour) && outside.insert(neighbour) {
+                    queue.push(neighbour);
+                }
+            }
+        }
+        Some(
+            cubes
+                .iter()
+                .flat_map(|cube| cube.neighbours())
+                .filter(|neighbour| outside.contains(neighbour))
+                .count(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 18);
+        assert_eq!(part_one(&input), Some(64));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 18);
+        assert_eq!(part_two(&input), Some(58));
+    }
+
+    #[test]
+    fn test_reference() {
+        let generate = |rng: &mut Rng| {
+            let size = 2 + rng.below(60);
+            generate(size, rng, &Params::default())
+        };
+        crate::differential::check(18, 200, generate, part_one, reference::part_one);
+        crate::differential::check(18, 200, generate, part_two, reference::part_two);
+    }
+}
@@ -0,0 +1,272 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::helpers::{GetInts, Rng};
+use crate::{Param, Params};
+use itertools::Itertools;
+
+pub const ORE: usize = 0;
+pub const CLAY: usize = 1;
+pub const OBSIDIAN: usize = 2;
+pub const GEODE: usize = 3;
+
+pub type Resources = [usize; 4];
+
+#[derive(Debug, Clone, Copy)]
+pub struct Blueprint {
+    pub id: usize,
+    /// `costs[robot]` is what building one robot of that type consumes.
+    pub costs: [Resources; 4],
+    /// No point in producing more of a resource per minute than a single robot can consume.
+    pub max_robots: Resources,
+}
+
+impl Blueprint {
+    pub fn parse(input: &str) -> Option<Blueprint> {
+        let [id, ore_ore, clay_ore, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] =
+            input.ints().ok()?;
+        let costs = [
+            [ore_ore, 0, 0, 0],
+            [clay_ore, 0, 0, 0],
+            [obsidian_ore, obsidian_clay, 0, 0],
+            [geode_ore, 0, geode_obsidian, 0],
+        ];
+        let max_robots = [
+            costs.iter().map(|cost| cost[ORE]).max()?,
+            costs[OBSIDIAN][CLAY],
+            costs[GEODE][OBSIDIAN],
+            usize::MAX,
+        ];
+        Some(Blueprint {
+            id,
+            costs,
+            max_robots,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    minutes_left: usize,
+    robots: Resources,
+    stock: Resources,
+}
+
+impl State {
+    /// Minutes of waiting before `robot` is affordable, if current production ever gets there.
+    fn wait_for(&self, cost: &Resources) -> Option<usize> {
+        (ORE..=GEODE)
+            .filter(|&resource| cost[resource] > self.stock[resource])
+            .map(|resource| {
+                let missing = cost[resource] - self.stock[resource];
+                match self.robots[resource] {
+                    0 => None,
+                    robots => Some(missing.div_ceil(robots)),
+                }
+            })
+            .try_fold(0, |wait, minutes| Some(wait.max(minutes?)))
+    }
+
+    /// Geodes cracked in a relaxed game: a free ore and clay robot arrive every minute, and
+    /// obsidian and geode robots are built as soon as affordable, each from its own copy of
+    /// the ore stock.
+    fn upper_bound(&self, blueprint: &Blueprint) -> usize {
+        let [mut ore_robots, mut clay_robots, mut obsidian_robots, mut geode_robots] = self.robots;
+        let [ore, mut clay, mut obsidian, mut geodes] = self.stock;
+        let (mut obsidian_ore, mut geode_ore) = (ore, ore);
+        let [obsidian_cost, geode_cost] = [blueprint.costs[OBSIDIAN], blueprint.costs[GEODE]];
+        for _ in 0..self.minutes_left {
+            let build_geode = geode_ore >= geode_cost[ORE] && obsidian >= geode_cost[OBSIDIAN];
+            let build_obsidian = obsidian_ore >= obsidian_cost[ORE] && clay >= obsidian_cost[CLAY];
+
+            obsidian_ore += ore_robots;
+            geode_ore += ore_robots;
+            clay += clay_robots;
+            obsidian += obsidian_robots;
+            geodes += geode_robots;
+
+            if build_geode {
+                geode_ore -= geode_cost[ORE];
+                obsidian -= geode_cost[OBSIDIAN];
+                geode_robots += 1;
+            }
+            if build_obsidian {
+                obsidian_ore -= obsidian_cost[ORE];
+                clay -= obsidian_cost[CLAY];
+                obsidian_robots += 1;
+            }
+            ore_robots += 1;
+            clay_robots += 1;
+        }
+        geodes
+    }
+}
+
+fn search(blueprint: &Blueprint, state: State, best: &mut usize) {
+    *best = (*best).max(state.stock[GEODE] + state.robots[GEODE] * state.minutes_left);
+    if state.upper_bound(blueprint) <= *best {
+        return;
+    }
+
+    for robot in (ORE..=GEODE).rev() {
+        // a robot is not worth building once there are as many as a robot build can consume per
+        // minute, or once the stock and the existing robots already cover the most that can be
+        // spent on its resource in the remaining minutes. Geode robots are always worth it.
+        let minutes = state.minutes_left;
+        if state.robots[robot] >= blueprint.max_robots[robot]
+            || (robot != GEODE
+                && state.stock[robot] + state.robots[robot] * minutes
+                    >= blueprint.max_robots[robot] * minutes)
+        {
+            continue;
+        }
+        let cost = &blueprint.costs[robot];
+        let Some(wait) = state.wait_for(cost) else {
+            continue;
+        };
+        if wait + 1 >= state.minutes_left {
+            continue;
+        }
+
+        // skip straight to the minute the robot is finished.
+        let minutes = wait + 1;
+        let mut next = state;
+        next.minutes_left -= minutes;
+        for (resource, stock) in next.stock.iter_mut().enumerate() {
+            *stock = *stock + state.robots[resource] * minutes - cost[resource];
+        }
+        next.robots[robot] += 1;
+        search(blueprint, next, best);
+    }
+}
+
+pub fn max_geodes(blueprint: &Blueprint, minutes: usize) -> usize {
+    let mut best = 0;
+    let state = State {
+        minutes_left: minutes,
+        robots: [1, 0, 0, 0],
+        stock: [0; 4],
+    };
+    search(blueprint, state, &mut best);
+    best
+}
+
+pub fn parse_input(input: &str) -> Option<Vec<Blueprint>> {
+    input
+        .split("Blueprint")
+        .filter(|blueprint| !blueprint.trim().is_empty())
+        .map(Blueprint::parse)
+        .collect()
+}
+
+/// Maximum geodes for each blueprint, shared out between one worker per available core: each
+/// worker takes the next blueprint nobody has started until none are left.
+fn evaluate(blueprints: &[Blueprint], minutes: usize) -> Vec<usize> {
+    let workers = thread::available_parallelism()
+        .map_or(1, |cores| cores.get())
+        .min(blueprints.len());
+    let next = AtomicUsize::new(0);
+    let mut geodes = vec![0; blueprints.len()];
+    thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(blueprint) = blueprints.get(index) else {
+                            return done;
+                        };
+                        done.push((index, max_geodes(blueprint, minutes)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            for (index, most) in handle.join().unwrap() {
+                geodes[index] = most;
+            }
+        }
+    });
+    geodes
+}
+
+pub fn quality_levels(input: &str, minutes: usize) -> Option<usize> {
+    let blueprints = parse_input(input)?;
+    let geodes = evaluate(&blueprints, minutes);
+    Some(
+        blueprints
+            .iter()
+            .zip(geodes)
+            .map(|(blueprint, geodes)| blueprint.id * geodes)
+            .sum(),
+    )
+}
+
+pub fn first_blueprints_product(input: &str, minutes: usize, count: usize) -> Option<usize> {
+    let blueprints = parse_input(input)?;
+    let first = &blueprints[..count.min(blueprints.len())];
+    Some(evaluate(first, minutes).into_iter().product())
+}
+
+/// The minutes of each part, and how many blueprints part two keeps.
+pub const PARAMS: &[Param] = &[
+    Param::new::<usize>("minutes_one", "24"),
+    Param::new::<usize>("minutes_two", "32"),
+    Param::new::<usize>("blueprints", "3"),
+];
+
+pub fn part_one(input: &str, params: &Params) -> Option<usize> {
+    quality_levels(input, params.get(PARAMS, "minutes_one"))
+}
+
+pub fn part_two(input: &str, params: &Params) -> Option<usize> {
+    first_blueprints_product(
+        input,
+        params.get(PARAMS, "minutes_two"),
+        params.get(PARAMS, "blueprints"),
+    )
+}
+
+/// `size` blueprints with robot costs in the ranges real inputs use.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    (1..=size.max(1))
+        .map(|id| {
+            let [ore, clay, obsidian_ore, geode_ore] = [(); 4].map(|_| rng.between(2, 4));
+            let [obsidian_clay, geode_obsidian] = [(); 2].map(|_| rng.between(5, 20));
+            format!(
+                "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+                 Each obsidian robot costs {} ore and {} clay. \
+                 Each geode robot costs {} ore and {} obsidian.",
+                id, ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian
+            )
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 19);
+        assert_eq!(part_one(&input, &Params::default()), Some(33));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 19);
+        assert_eq!(part_two(&input, &Params::default()), Some(56 * 62));
+    }
+
+    #[test]
+    fn test_evaluate() {
+        // more blueprints than workers, answered in input order.
+        let blueprints = parse_input(&crate::read_file("examples", 19)).unwrap();
+        let many = blueprints.iter().copied().cycle().take(40).collect_vec();
+        let geodes = evaluate(&many, 24);
+        assert_eq!(geodes, [9, 12].repeat(20));
+        assert!(evaluate(&[], 24).is_empty());
+    }
+}
@@ -0,0 +1,71 @@
+use crate::helpers::{CircularList, Rng};
+use crate::{Param, Params};
+use itertools::Itertools;
+
+pub fn mix(input: &str, decryption_key: isize, rounds: usize) -> Option<isize> {
+    let numbers = input
+        .lines()
+        .map(|line| line.parse::<isize>().ok()?.checked_mul(decryption_key))
+        .collect::<Option<Vec<_>>>()?;
+    let zero = numbers.iter().position(|num| *num == 0)?;
+
+    let mut list = CircularList::new(numbers);
+    for _ in 0..rounds {
+        for original in 0..list.len() {
+            list.move_by(original, *list.value(original));
+        }
+    }
+
+    let zero_pos = list.position_of(zero);
+    [1000, 2000, 3000]
+        .into_iter()
+        .map(|offset| list.get(zero_pos + offset))
+        .sum()
+}
+
+/// The decryption key and mixing rounds of each part.
+pub const PARAMS: &[Param] = &[
+    Param::new::<isize>("key_one", "1"),
+    Param::new::<usize>("rounds_one", "1"),
+    Param::new::<isize>("key_two", "811589153"),
+    Param::new::<usize>("rounds_two", "10"),
+];
+
+pub fn part_one(input: &str, params: &Params) -> Option<isize> {
+    let key = params.get(PARAMS, "key_one");
+    mix(input, key, params.get(PARAMS, "rounds_one"))
+}
+
+pub fn part_two(input: &str, params: &Params) -> Option<isize> {
+    let key = params.get(PARAMS, "key_two");
+    mix(input, key, params.get(PARAMS, "rounds_two"))
+}
+
+/// `size` numbers (at least two) in `-9999..=9999`, exactly one of them zero.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    let count = size.max(2);
+    let zero = rng.below(count);
+    (0..count)
+        .map(|idx| match idx == zero {
+            true => 0,
+            false => rng.between(1, 9999) * if rng.chance(1, 2) { -1 } else { 1 },
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 20);
+        assert_eq!(part_one(&input, &Params::default()), Some(3));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 20);
+        assert_eq!(part_two(&input, &Params::default()), Some(1623178306));
+    }
+}
@@ -0,0 +1,534 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    ops::{Add, Div, Mul, Sub},
+};
+
+use crate::helpers::Rng;
+use crate::Params;
+
+pub type Id = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Operator {
+    fn from_str(input: &str) -> Option<Operator> {
+        match input {
+            "+" => Some(Operator::Add),
+            "-" => Some(Operator::Sub),
+            "*" => Some(Operator::Mul),
+            "/" => Some(Operator::Div),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Node {
+    Number(i128),
+    Operation(Id, Operator, Id),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse(String),
+    Duplicate(String),
+    MissingReference(String),
+    Cycle(String),
+    DivisionByZero(String),
+    NonLinear(String),
+    NoUniqueSolution,
+    NotAnInteger(Rational),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(line) => write!(f, "could not parse line `{}`", line),
+            Error::Duplicate(name) => write!(f, "`{}` is defined more than once", name),
+            Error::MissingReference(name) => {
+                write!(f, "`{}` is referenced but never defined", name)
+            }
+            Error::Cycle(name) => write!(f, "`{}` depends on itself", name),
+            Error::DivisionByZero(name) => write!(f, "`{}` divides by zero", name),
+            Error::NonLinear(name) => write!(f, "the unknown appears non-linearly in `{}`", name),
+            Error::NoUniqueSolution => write!(f, "the equation does not have a unique solution"),
+            Error::NotAnInteger(value) => write!(f, "{} is not an integer", value),
+        }
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Exact fraction, always stored with a positive denominator and in lowest terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    const ZERO: Rational = Rational {
+        numerator: 0,
+        denominator: 1,
+    };
+
+    pub fn new(numerator: i128, denominator: i128) -> Rational {
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Rational {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+
+    pub fn integer(value: i128) -> Rational {
+        Rational::new(value, 1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn to_integer(self) -> Result<i128, Error> {
+        match self.denominator {
+            1 => Ok(self.numerator),
+            _ => Err(Error::NotAnInteger(self)),
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            _ => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        Rational::new(
+            self.numerator * other.denominator + other.numerator * self.denominator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self + Rational::new(-other.numerator, other.denominator)
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        Rational::new(
+            self.numerator * other.numerator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    /// Callers must rule out a zero divisor beforehand.
+    fn div(self, other: Rational) -> Rational {
+        Rational::new(
+            self.numerator * other.denominator,
+            self.denominator * other.numerator,
+        )
+    }
+}
+
+/// `slope * x + constant`, where `x` is the single unknown of the graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Linear {
+    pub slope: Rational,
+    pub constant: Rational,
+}
+
+impl Linear {
+    pub fn constant(value: Rational) -> Linear {
+        Linear {
+            slope: Rational::ZERO,
+            constant: value,
+        }
+    }
+
+    pub fn unknown() -> Linear {
+        Linear {
+            slope: Rational::integer(1),
+            constant: Rational::ZERO,
+        }
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.slope.is_zero()
+    }
+
+    /// Fails with the error constructor to report against the node being evaluated.
+    pub fn apply(self, operator: Operator, other: Linear) -> Result<Linear, fn(String) -> Error> {
+        match operator {
+            Operator::Add => Ok(Linear {
+                slope: self.slope + other.slope,
+                constant: self.constant + other.constant,
+            }),
+            Operator::Sub => Ok(Linear {
+                slope: self.slope - other.slope,
+                constant: self.constant - other.constant,
+            }),
+            Operator::Mul if !self.is_constant() && !other.is_constant() => Err(Error::NonLinear),
+            Operator::Mul => Ok(Linear {
+                slope: self.slope * other.constant + other.slope * self.constant,
+                constant: self.constant * other.constant,
+            }),
+            Operator::Div if !other.is_constant() => Err(Error::NonLinear),
+            Operator::Div if other.constant.is_zero() => Err(Error::DivisionByZero),
+            Operator::Div => Ok(Linear {
+                slope: self.slope / other.constant,
+                constant: self.constant / other.constant,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Graph<'a> {
+    names: Vec<&'a str>,
+    ids: HashMap<&'a str, Id>,
+    nodes: Vec<Option<Node>>,
+}
+
+impl<'a> Graph<'a> {
+    pub fn parse(input: &'a str) -> Result<Graph<'a>, Error> {
+        let mut graph = Graph::default();
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let parse_error = || Error::Parse(line.to_string());
+            let (name, expression) = line.split_once(':').ok_or_else(parse_error)?;
+            let node = match expression.split_whitespace().collect::<Vec<_>>()[..] {
+                [number] => Node::Number(number.parse().map_err(|_| parse_error())?),
+                [left, operator, right] => Node::Operation(
+                    graph.intern(left),
+                    Operator::from_str(operator).ok_or_else(parse_error)?,
+                    graph.intern(right),
+                ),
+                _ => return Err(parse_error()),
+            };
+
+            let id = graph.intern(name.trim());
+            if graph.nodes[id].replace(node).is_some() {
+                return Err(Error::Duplicate(name.trim().to_string()));
+            }
+        }
+        Ok(graph)
+    }
+
+    fn intern(&mut self, name: &'a str) -> Id {
+        *self.ids.entry(name).or_insert_with(|| {
+            self.names.push(name);
+            self.nodes.push(None);
+            self.nodes.len() - 1
+        })
+    }
+
+    pub fn id(&self, name: &str) -> Result<Id, Error> {
+        self.ids
+            .get(name)
+            .copied()
+            .ok_or_else(|| Error::MissingReference(name.to_string()))
+    }
+
+    pub fn node(&self, id: Id) -> Result<Node, Error> {
+        self.nodes[id].ok_or_else(|| Error::MissingReference(self.names[id].to_string()))
+    }
+
+    /// Every node `root` depends on, dependencies first and `root` last.
+    pub fn topological_order(&self, root: Id) -> Result<Vec<Id>, Error> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            New,
+            Visiting,
+            Done,
+        }
+
+        let mut marks = vec![Mark::New; self.nodes.len()];
+        let mut order = vec![];
+        let mut stack = vec![(root, false)];
+        while let Some((id, expanded)) = stack.pop() {
+            if expanded {
+                marks[id] = Mark::Done;
+                order.push(id);
+                continue;
+            }
+            match marks[id] {
+                Mark::Done => continue,
+                Mark::Visiting => return Err(Error::Cycle(self.names[id].to_string())),
+                Mark::New => {}
+            }
+
+            marks[id] = Mark::Visiting;
+            stack.push((id, true));
+            if let Node::Operation(left, _, right) = self.node(id)? {
+                for child in [right, left] {
+                    match marks[child] {
+                        Mark::Visiting => return Err(Error::Cycle(self.names[child].to_string())),
+                        Mark::New => stack.push((child, false)),
+                        Mark::Done => {}
+                    }
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Evaluates `order` in sequence, treating `unknown` (if any) as the variable `x`.
+    pub fn evaluate(
+        &self,
+        order: &[Id],
+        unknown: Option<Id>,
+    ) -> Result<Vec<Option<Linear>>, Error> {
+        let mut values: Vec<Option<Linear>> = vec![None; self.nodes.len()];
+        for &id in order {
+            let value = match self.node(id)? {
+                _ if Some(id) == unknown => Linear::unknown(),
+                Node::Number(number) => Linear::constant(Rational::integer(number)),
+                Node::Operation(left, operator, right) => {
+                    let (left, right) = (values[left].unwrap(), values[right].unwrap());
+                    left.apply(operator, right)
+                        .map_err(|error| error(self.names[id].to_string()))?
+                }
+            };
+            values[id] = Some(value);
+        }
+        Ok(values)
+    }
+
+    pub fn value_of(&self, name: &str) -> Result<i128, Error> {
+        let root = self.id(name)?;
+        let order = self.topological_order(root)?;
+        let values = self.evaluate(&order, None)?;
+        values[root].unwrap().constant.to_integer()
+    }
+
+    /// Solves `left == right` for `unknown`, where `root` is `left <op> right`.
+    pub fn solve_equality(&self, root: &str, unknown: &str) -> Result<i128, Error> {
+        let root = self.id(root)?;
+        let Node::Operation(left, _, right) = self.node(root)? else {
+            return Err(Error::NoUniqueSolution);
+        };
+
+        let mut order = self.topological_order(root)?;
+        order.pop();
+        let values = self.evaluate(&order, self.ids.get(unknown).copied())?;
+        let (left, right) = (values[left].unwrap(), values[right].unwrap());
+
+        let slope = left.slope - right.slope;
+        if slope.is_zero() {
+            return Err(Error::NoUniqueSolution);
+        }
+        ((right.constant - left.constant) / slope).to_integer()
+    }
+}
+
+pub fn part_one(input: &str) -> Option<isize> {
+    let graph = Graph::parse(input).ok()?;
+    graph.value_of("root").ok()?.try_into().ok()
+}
+
+pub fn part_two(input: &str) -> Option<isize> {
+    let graph = Graph::parse(input).ok()?;
+    graph.solve_equality("root", "humn").ok()?.try_into().ok()
+}
+
+/// Roughly `size` monkeys, in random order. The `humn` side of `root` only adds, subtracts and
+/// multiplies, and the other side is built to match it for some secret `humn` value, so both
+/// parts have an integer answer. Divisions elsewhere are always exact.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    let mut monkeys = Monkeys {
+        rng,
+        names: HashSet::from(["root".to_string(), "humn".to_string()]),
+        lines: vec![],
+    };
+    let nodes = size.max(3) - 1;
+    let humn_nodes = 1 + monkeys.rng.below(nodes - 1);
+    let secret = monkeys.rng.between(1, 5000) as i128;
+    let (humn_side, value, _) = monkeys.with_humn(humn_nodes, secret);
+    let other_side = monkeys.exactly(nodes - humn_nodes, value);
+
+    let (left, right) = match monkeys.rng.chance(1, 2) {
+        true => (humn_side, other_side),
+        false => (other_side, humn_side),
+    };
+    let operator = monkeys.rng.choose(&['+', '-']);
+    monkeys
+        .lines
+        .push(format!("root: {} {} {}", left, operator, right));
+    let humn = monkeys.rng.between(1, 5000);
+    monkeys.lines.push(format!("humn: {}", humn));
+
+    let Monkeys { rng, mut lines, .. } = monkeys;
+    rng.shuffle(&mut lines);
+    lines.join("\n")
+}
+
+struct Monkeys<'a> {
+    rng: &'a mut Rng,
+    names: HashSet<String>,
+    lines: Vec<String>,
+}
+
+impl Monkeys<'_> {
+    fn name(&mut self) -> String {
+        loop {
+            let name: String = (0..4)
+                .map(|_| (b'a' + self.rng.below(26) as u8) as char)
+                .collect();
+            if self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    fn define(&mut self, expression: String) -> String {
+        let name = self.name();
+        self.lines.push(format!("{}: {}", name, expression));
+        name
+    }
+
+    /// Splits `nodes - 1` monkeys between two children, giving each at least one.
+    fn split(&mut self, nodes: usize) -> (usize, usize) {
+        let left = 1 + self.rng.below(nodes - 2);
+        (left, nodes - 1 - left)
+    }
+
+    /// A random subtree of `nodes` monkeys, and the number it yells.
+    fn constant(&mut self, nodes: usize) -> (String, i128) {
+        if nodes < 3 {
+            let value = self.rng.between(1, 20) as i128;
+            return (self.define(value.to_string()), value);
+        }
+        let (left_nodes, right_nodes) = self.split(nodes);
+        let (left, a) = self.constant(left_nodes);
+        let (right, b) = self.constant(right_nodes);
+        let mut operators = vec![('+', a + b), ('-', a - b)];
+        if (a * b).abs() <= 1_000_000 {
+            operators.push(('*', a * b));
+        }
+        if b != 0 && a % b == 0 {
+            operators.push(('/', a / b));
+        }
+        let (operator, value) = *self.rng.choose(&operators);
+        let name = self.define(format!("{} {} {}", left, operator, right));
+        (name, value)
+    }
+
+    /// A subtree of `nodes` monkeys that yells exactly `target`.
+    fn exactly(&mut self, nodes: usize, target: i128) -> String {
+        if nodes < 3 {
+            return self.define(target.to_string());
+        }
+        let (left_nodes, right_nodes) = self.split(nodes);
+        let (left, a) = self.constant(left_nodes);
+        let mut operators = vec![('+', target - a), ('-', a - target)];
+        if a != 0 && target % a == 0 {
+            operators.push(('*', target / a));
+        }
+        let (operator, needed) = *self.rng.choose(&operators);
+        let right = self.exactly(right_nodes, needed);
+        self.define(format!("{} {} {}", left, operator, right))
+    }
+
+    /// A subtree of `nodes` monkeys containing `humn`, with the number it yells when `humn`
+    /// yells `secret` and how that number changes per unit of `humn`.
+    fn with_humn(&mut self, nodes: usize, secret: i128) -> (String, i128, i128) {
+        if nodes < 3 {
+            return ("humn".to_string(), secret, 1);
+        }
+        let (other_nodes, humn_nodes) = self.split(nodes);
+        let (other, a) = self.constant(other_nodes);
+        let (humn, h, slope) = self.with_humn(humn_nodes, secret);
+        let mut operators = vec![
+            (format!("{} + {}", humn, other), h + a, slope),
+            (format!("{} + {}", other, humn), h + a, slope),
+            (format!("{} - {}", humn, other), h - a, slope),
+            (format!("{} - {}", other, humn), a - h, -slope),
+        ];
+        if a != 0 && (slope * a).abs() <= 1_000_000 && (h * a).abs() <= 1_000_000_000_000 {
+            operators.push((format!("{} * {}", humn, other), h * a, slope * a));
+            operators.push((format!("{} * {}", other, humn), h * a, slope * a));
+        }
+        let (expression, value, slope) = self.rng.choose(&operators).clone();
+        (self.define(expression), value, slope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 21);
+        assert_eq!(part_one(&input), Some(152));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 21);
+        assert_eq!(part_two(&input), Some(301));
+    }
+
+    #[test]
+    fn test_errors() {
+        let graph = Graph::parse("root: aaaa + bbbb\naaaa: 1").unwrap();
+        assert_eq!(
+            graph.value_of("root"),
+            Err(Error::MissingReference("bbbb".to_string()))
+        );
+
+        let graph = Graph::parse("root: aaaa + bbbb\naaaa: bbbb * 2\nbbbb: aaaa - 1").unwrap();
+        assert!(matches!(graph.value_of("root"), Err(Error::Cycle(_))));
+
+        let graph = Graph::parse("root: aaaa + bbbb\naaaa: humn * humn\nbbbb: 4\nhumn: 1").unwrap();
+        assert_eq!(
+            graph.solve_equality("root", "humn"),
+            Err(Error::NonLinear("aaaa".to_string()))
+        );
+
+        let graph = Graph::parse("root: aaaa + bbbb\naaaa: humn * cccc\nbbbb: 3\ncccc: 2\nhumn: 4")
+            .unwrap();
+        assert_eq!(
+            graph.solve_equality("root", "humn"),
+            Err(Error::NotAnInteger(Rational::new(3, 2)))
+        );
+
+        let graph = Graph::parse(
+            "root: aaaa + bbbb\naaaa: cccc - humn\nbbbb: humn / dddd\ncccc: 3\ndddd: 4\nhumn: 4",
+        )
+        .unwrap();
+        assert_eq!(
+            graph.solve_equality("root", "humn"),
+            Err(Error::NotAnInteger(Rational::new(12, 5)))
+        );
+    }
+}
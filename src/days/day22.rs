@@ -0,0 +1,450 @@
+use std::collections::HashMap;
+
+use crate::helpers::Rng;
+use crate::Params;
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+pub type Position = (isize, isize);
+pub type Vector = (isize, isize, isize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum Facing {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Facing {
+    pub fn turn(&self, c: &char) -> Self {
+        match (self, c) {
+            (Facing::Down, 'L') => Facing::Right,
+            (Facing::Down, 'R') => Facing::Left,
+            (Facing::Right, 'L') => Facing::Up,
+            (Facing::Right, 'R') => Facing::Down,
+            (Facing::Up, 'L') => Facing::Left,
+            (Facing::Up, 'R') => Facing::Right,
+            (Facing::Left, 'L') => Facing::Down,
+            (Facing::Left, 'R') => Facing::Up,
+            _ => unimplemented!(),
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.turn(&'L').turn(&'L')
+    }
+
+    pub fn to_number(self) -> isize {
+        match self {
+            Facing::Right => 0,
+            Facing::Down => 1,
+            Facing::Left => 2,
+            Facing::Up => 3,
+        }
+    }
+
+    pub fn delta(&self) -> Position {
+        match self {
+            Facing::Right => (1, 0),
+            Facing::Down => (0, 1),
+            Facing::Left => (-1, 0),
+            Facing::Up => (0, -1),
+        }
+    }
+}
+
+fn negate(vector: Vector) -> Vector {
+    (-vector.0, -vector.1, -vector.2)
+}
+
+/// Orientation of a net tile once folded, as outward unit vectors in 3D.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Face {
+    normal: Vector,
+    right: Vector,
+    down: Vector,
+}
+
+impl Face {
+    /// Direction in 3D that `facing` points to while standing on this face.
+    fn towards(&self, facing: &Facing) -> Vector {
+        match facing {
+            Facing::Right => self.right,
+            Facing::Down => self.down,
+            Facing::Left => negate(self.right),
+            Facing::Up => negate(self.down),
+        }
+    }
+
+    /// Orientation of the tile next to this one in the net, folded over the shared edge.
+    fn roll(&self, facing: &Facing) -> Face {
+        match facing {
+            Facing::Right => Face {
+                normal: self.right,
+                right: negate(self.normal),
+                down: self.down,
+            },
+            Facing::Left => Face {
+                normal: negate(self.right),
+                right: self.normal,
+                down: self.down,
+            },
+            Facing::Down => Face {
+                normal: self.down,
+                right: self.right,
+                down: negate(self.normal),
+            },
+            Facing::Up => Face {
+                normal: negate(self.down),
+                right: self.right,
+                down: self.normal,
+            },
+        }
+    }
+}
+
+/// The map folded into a cube: each net tile (in units of `side`) with its 3D orientation.
+#[derive(Debug)]
+pub struct Cube {
+    side: isize,
+    faces: HashMap<Position, Face>,
+}
+
+impl Cube {
+    pub fn fold(map: &HashMap<Position, char>) -> Option<Cube> {
+        let side = (1..).find(|side| side * side * 6 >= map.len() as isize)?;
+        if side * side * 6 != map.len() as isize {
+            return None;
+        }
+
+        let tiles = map
+            .keys()
+            .map(|(x, y)| (x.div_euclid(side), y.div_euclid(side)))
+            .unique()
+            .collect_vec();
+        let first = *tiles.iter().min_by_key(|(x, y)| (*y, *x))?;
+
+        let mut faces = HashMap::from([(
+            first,
+            Face {
+                normal: (0, 0, -1),
+                right: (1, 0, 0),
+                down: (0, 1, 0),
+            },
+        )]);
+        let mut to_visit = vec![first];
+        while let Some(tile) = to_visit.pop() {
+            let face = faces[&tile];
+            for facing in Facing::iter() {
+                let delta = facing.delta();
+                let neighbour = (tile.0 + delta.0, tile.1 + delta.1);
+                if tiles.contains(&neighbour) && !faces.contains_key(&neighbour) {
+                    faces.insert(neighbour, face.roll(&facing));
+                    to_visit.push(neighbour);
+                }
+            }
+        }
+
+        let folded = faces.len() == 6
+            && tiles.len() == 6
+            && faces.values().map(|face| face.normal).unique().count() == 6;
+        folded.then_some(Cube { side, faces })
+    }
+
+    /// Where walking off the edge of a tile lands, and the facing after crossing the edge.
+    pub fn wrap(&self, position: &Position, facing: &Facing) -> (Position, Facing) {
+        let side = self.side;
+        let tile = (position.0.div_euclid(side), position.1.div_euclid(side));
+        let (x, y) = (position.0.rem_euclid(side), position.1.rem_euclid(side));
+        let face = self.faces[&tile];
+
+        let (next_tile, next_face) = self
+            .faces
+            .iter()
+            .find(|(_, other)| other.normal == face.towards(facing))
+            .unwrap();
+        let entry_edge = Facing::iter()
+            .find(|edge| next_face.towards(edge) == face.normal)
+            .unwrap();
+
+        // offsets run clockwise around each tile, so a shared edge is traversed in reverse.
+        let offset = match facing {
+            Facing::Up => x,
+            Facing::Right => y,
+            Facing::Down => side - 1 - x,
+            Facing::Left => side - 1 - y,
+        };
+        let offset = side - 1 - offset;
+        let (x, y) = match entry_edge {
+            Facing::Up => (offset, 0),
+            Facing::Right => (side - 1, offset),
+            Facing::Down => (side - 1 - offset, side - 1),
+            Facing::Left => (0, side - 1 - offset),
+        };
+
+        (
+            (next_tile.0 * side + x, next_tile.1 * side + y),
+            entry_edge.opposite(),
+        )
+    }
+}
+
+fn find_opposite(
+    current_position: &Position,
+    facing: &Facing,
+    map: &HashMap<Position, char>,
+) -> Position {
+    let max = (
+        map.keys().max_by_key(|p| p.0).unwrap().0,
+        map.keys().max_by_key(|p| p.1).unwrap().1,
+    );
+    let delta = facing.delta();
+    let mut position = match facing {
+        Facing::Right => (0, current_position.1),
+        Facing::Down => (current_position.0, 0),
+        Facing::Left => (max.0, current_position.1),
+        Facing::Up => (current_position.0, max.1),
+    };
+
+    loop {
+        if map.contains_key(&position) {
+            return position;
+        } else {
+            position = (position.0 + delta.0, position.1 + delta.1);
+        }
+    }
+}
+
+/// The open and solid tiles with the path, or `None` unless the input is a map, a blank line
+/// and a path of step counts and `L`/`R` turns.
+pub fn parse_input(input: &str) -> Option<(HashMap<Position, char>, Vec<String>)> {
+    let (whole_map, directions) = input.split("\n\n").collect_tuple()?;
+
+    let mut steps = String::new();
+    let mut path = vec![];
+    for c in directions.trim().chars() {
+        match c {
+            '0'..='9' => steps.push(c),
+            'L' | 'R' => {
+                path.extend((!steps.is_empty()).then(|| std::mem::take(&mut steps)));
+                path.push(c.to_string());
+            }
+            _ => return None,
+        }
+    }
+    path.extend((!steps.is_empty()).then_some(steps));
+    let directions = path;
+
+    let mut map = HashMap::new();
+    for (y, line) in whole_map.lines().enumerate() {
+        for (x, c) in line.char_indices() {
+            if c == '.' || c == '#' {
+                map.insert((x as isize, y as isize), c);
+            }
+        }
+    }
+    Some((map, directions))
+}
+
+/// Follows `directions` from the top-left open tile, calling `wrap` whenever a step leaves the map.
+pub fn walk(
+    map: &HashMap<Position, char>,
+    directions: &[String],
+    wrap: impl Fn(&Position, &Facing) -> (Position, Facing),
+) -> Option<isize> {
+    let mut pos = *map
+        .iter()
+        .filter(|(_, c)| **c == '.')
+        .map(|(position, _)| position)
+        .min_by_key(|(x, y)| (*y, *x))?;
+    let mut facing = Facing::Right;
+
+    for direction in directions {
+        if direction == "L" || direction == "R" {
+            facing = facing.turn(&direction.chars().next().unwrap());
+            continue;
+        }
+
+        for _ in 0..direction.parse::<usize>().ok()? {
+            let delta = facing.delta();
+            let (new_pos, new_facing) = match (pos.0 + delta.0, pos.1 + delta.1) {
+                new_pos if map.contains_key(&new_pos) => (new_pos, facing),
+                _ => wrap(&pos, &facing),
+            };
+            if map[&new_pos] == '#' {
+                break;
+            }
+            pos = new_pos;
+            facing = new_facing;
+        }
+    }
+    Some((pos.1 + 1) * 1000 + (pos.0 + 1) * 4 + facing.to_number())
+}
+
+pub fn part_one(input: &str) -> Option<isize> {
+    let (map, directions) = parse_input(input)?;
+    walk(&map, &directions, |pos, facing| {
+        (find_opposite(pos, facing, &map), *facing)
+    })
+}
+
+pub fn part_two(input: &str) -> Option<isize> {
+    let (map, directions) = parse_input(input)?;
+    let cube = Cube::fold(&map)?;
+    walk(&map, &directions, |pos, facing| cube.wrap(pos, facing))
+}
+
+/// The eleven cube nets, drawn in tiles.
+const NETS: [&str; 11] = [
+    "#...\n####\n#...",
+    "#...\n####\n.#..",
+    "#...\n####\n..#.",
+    "#...\n####\n...#",
+    ".#..\n####\n.#..",
+    ".#..\n####\n..#.",
+    "##..\n.###\n.#..",
+    "##..\n.###\n..#.",
+    "##..\n.###\n...#",
+    "##..\n.##.\n..##",
+    "###..\n..###",
+];
+
+/// The net's tiles under each of the 8 rotations and reflections of the plane.
+fn symmetries(net: &str) -> Vec<Vec<Position>> {
+    let tiles = net
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.char_indices()
+                .filter(|(_, c)| *c == '#')
+                .map(move |(x, _)| (x as isize, y as isize))
+        })
+        .collect_vec();
+
+    (0..8)
+        .map(|symmetry| {
+            let transformed = tiles
+                .iter()
+                .map(|&(x, y)| {
+                    let (x, y) = if symmetry >= 4 { (-x, y) } else { (x, y) };
+                    (0..symmetry % 4).fold((x, y), |(x, y), _| (-y, x))
+                })
+                .collect_vec();
+            let min_x = transformed.iter().map(|p| p.0).min().unwrap();
+            let min_y = transformed.iter().map(|p| p.1).min().unwrap();
+            transformed
+                .into_iter()
+                .map(|(x, y)| (x - min_x, y - min_y))
+                .collect_vec()
+        })
+        .collect_vec()
+}
+
+/// One of the cube nets in a random orientation with `size`-wide faces, walls on about a tenth
+/// of the tiles and `10 · size` random moves.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    let side = size.max(1) as isize;
+    let nets = NETS.iter().flat_map(|net| symmetries(net)).collect_vec();
+    let tiles = rng.choose(&nets);
+    let width = tiles.iter().map(|tile| tile.0).max().unwrap() + 1;
+    let height = tiles.iter().map(|tile| tile.1).max().unwrap() + 1;
+
+    let mut rows = (0..height * side)
+        .map(|y| {
+            (0..width * side)
+                .map(|x| match tiles.contains(&(x / side, y / side)) {
+                    true if rng.chance(1, 10) => '#',
+                    true => '.',
+                    false => ' ',
+                })
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect_vec();
+    // the walk starts on the first open tile of the top row.
+    rows[0] = rows[0].replacen('#', ".", 1);
+
+    let moves = (0..10 * size.max(1))
+        .map(|_| rng.between(1, 2 * side).to_string())
+        .collect_vec();
+    let path = moves.iter().skip(1).fold(moves[0].clone(), |path, steps| {
+        format!("{}{}{}", path, rng.choose(&['L', 'R']), steps)
+    });
+
+    format!("{}\n\n{}", rows.join("\n"), path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Expands a net drawn in tiles into a map with `side` × `side` open cells per tile.
+    fn net_map(tiles: &[Position], side: isize) -> HashMap<Position, char> {
+        tiles
+            .iter()
+            .flat_map(|(tx, ty)| {
+                (0..side)
+                    .cartesian_product(0..side)
+                    .map(move |(x, y)| ((tx * side + x, ty * side + y), '.'))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 22);
+        assert_eq!(part_one(&input), Some(6032));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 22);
+        assert_eq!(part_two(&input), Some(5031));
+    }
+
+    #[test]
+    fn test_positions() {
+        for net in NETS {
+            for tiles in symmetries(net) {
+                for side in [1, 3, 4] {
+                    let map = net_map(&tiles, side);
+                    let cube = Cube::fold(&map).unwrap_or_else(|| panic!("net: {:?}", tiles));
+
+                    for (&position, facing) in map.keys().cartesian_product(Facing::iter()) {
+                        let on_edge = match facing {
+                            Facing::Right => position.0.rem_euclid(side) == side - 1,
+                            Facing::Down => position.1.rem_euclid(side) == side - 1,
+                            Facing::Left => position.0.rem_euclid(side) == 0,
+                            Facing::Up => position.1.rem_euclid(side) == 0,
+                        };
+                        if !on_edge {
+                            continue;
+                        }
+
+                        let (next, next_facing) = cube.wrap(&position, &facing);
+                        assert!(map.contains_key(&next), "position: {:?}", position);
+
+                        // crossing an edge that is already joined in the net is a straight step.
+                        let delta = facing.delta();
+                        let straight = (position.0 + delta.0, position.1 + delta.1);
+                        if map.contains_key(&straight) {
+                            assert_eq!((next, next_facing), (straight, facing));
+                        }
+
+                        let back = cube.wrap(&next, &next_facing.opposite());
+                        assert_eq!(
+                            back,
+                            (position, facing.opposite()),
+                            "net: {:?}, position: {:?}",
+                            tiles,
+                            position
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::checkpoint::{self, Checkpoint};
+use crate::helpers::{GetInts, Rng, Simulation};
+use crate::{Param, Params};
+use itertools::Itertools;
+use serde_json::{json, Value};
+
+pub type Position = (isize, isize);
+
+trait Neighbours {
+    fn neighbours(&self, deltas: &[Position]) -> Vec<Position>;
+    fn all_neighbours(&self) -> Vec<Position>;
+    fn north_neighbours(&self) -> Vec<Position>;
+    fn south_neighbours(&self) -> Vec<Position>;
+    fn west_neighbours(&self) -> Vec<Position>;
+    fn east_neighbours(&self) -> Vec<Position>;
+}
+
+impl Neighbours for Position {
+    fn neighbours(&self, deltas: &[Position]) -> Vec<Self> {
+        deltas
+            .iter()
+            .map(|delta| (delta.0 + self.0, delta.1 + self.1))
+            .collect()
+    }
+
+    fn north_neighbours(&self) -> Vec<Self> {
+        let deltas = vec![(-1, -1), (0, -1), (1, -1)];
+        self.neighbours(&deltas)
+    }
+
+    fn south_neighbours(&self) -> Vec<Self> {
+        let deltas = vec![(-1, 1), (0, 1), (1, 1)];
+        self.neighbours(&deltas)
+    }
+
+    fn west_neighbours(&self) -> Vec<Self> {
+        let deltas = vec![(-1, -1), (-1, 0), (-1, 1)];
+        self.neighbours(&deltas)
+    }
+
+    fn east_neighbours(&self) -> Vec<Self> {
+        let deltas = vec![(1, -1), (1, 0), (1, 1)];
+        self.neighbours(&deltas)
+    }
+
+    fn all_neighbours(&self) -> Vec<Position> {
+        let deltas = vec![
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, -1),
+            (0, 1),
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+        ];
+        self.neighbours(&deltas)
+    }
+}
+
+/// The rounds of part one, and the most part two plays before giving up.
+pub const PARAMS: &[Param] = &[
+    Param::new::<usize>("rounds", "10"),
+    Param::new::<usize>("max_rounds", "100000"),
+    checkpoint::EVERY,
+    checkpoint::PATH,
+    checkpoint::RESUME,
+];
+
+pub fn part_one(input: &str, params: &Params) -> Option<isize> {
+    let mut grove = Grove::new(parse_elves(input)?);
+    for _round in 0..params.get(PARAMS, "rounds") {
+        grove.round();
+    }
+    grove.empty_ground()
+}
+
+pub fn part_two(input: &str, params: &Params) -> Option<usize> {
+    let mut grove = Grove::new(parse_elves(input)?);
+    let max_rounds = params.get(PARAMS, "max_rounds");
+    checkpoint::run(&mut grove, 23, 2, params, max_rounds);
+    (grove.rounds > 0 && grove.moved_to.is_empty()).then_some(grove.rounds)
+}
+
+type NeighboursFn = for<'r> fn(&'r Position) -> Vec<Position>;
+
+/// The elves spreading out over the grove, a round at a time.
+#[derive(Debug, Clone)]
+pub struct Grove {
+    pub elves: HashSet<Position>,
+    /// The directions the elves consider, in the order they consider them this round.
+    moves: [(NeighboursFn, Position); 4],
+    pub rounds: usize,
+    /// Where elves moved to in the last round.
+    pub moved_to: HashSet<Position>,
+}
+
+impl Grove {
+    pub fn new(elves: HashSet<Position>) -> Self {
+        Grove {
+            elves,
+            moves: [
+                (Neighbours::north_neighbours, (0, -1)),
+                (Neighbours::south_neighbours, (0, 1)),
+                (Neighbours::west_neighbours, (-1, 0)),
+                (Neighbours::east_neighbours, (1, 0)),
+            ],
+            rounds: 0,
+            moved_to: HashSet::new(),
+        }
+    }
+
+    /// Plays a round, returning how many elves moved.
+    pub fn round(&mut self) -> usize {
+        let starting_map = &self.elves;
+        let mut new_moves: HashMap<Position, Vec<Position>> = HashMap::new();
+        for elf in starting_map {
+            let has_neighbours = elf
+                .all_neighbours()
+                .iter()
+                .any(|n| starting_map.contains(n));
+
+            let proposal = match has_neighbours {
+                true => self
+                    .moves
+                    .iter()
+                    .find(|m| m.0(elf).iter().all(|n| !starting_map.contains(n))),
+                false => None,
+            };
+            match proposal {
+                Some((_, delta)) => {
+                    let dst = (elf.0 + delta.0, elf.1 + delta.1);
+                    new_moves.entry(dst).or_default().push(*elf);
+                }
+                _ => new_moves.entry(*elf).or_default().push(*elf),
+            }
+        }
+
+        let mut new_map: HashSet<Position> = HashSet::new();
+        self.moved_to.clear();
+        for (dst, possible_elves) in new_moves.iter() {
+            if possible_elves.len() == 1 {
+                new_map.insert(*dst);
+                if possible_elves[0] != *dst {
+                    self.moved_to.insert(*dst);
+                }
+            } else {
+                for elf in possible_elves {
+                    new_map.insert(*elf);
+                }
+            }
+        }
+        self.elves = new_map;
+        self.moves.rotate_left(1);
+        self.rounds += 1;
+        self.moved_to.len()
+    }
+
+    /// Empty ground tiles in the smallest rectangle holding every elf.
+    pub fn empty_ground(&self) -> Option<isize> {
+        let ((min_x, _), (max_x, _)) = self.elves.iter().minmax_by_key(|e| e.0).into_option()?;
+        let ((_, min_y), (_, max_y)) = self.elves.iter().minmax_by_key(|e| e.1).into_option()?;
+
+        Some((max_x - min_x + 1) * (max_y - min_y + 1) - self.elves.len() as isize)
+    }
+}
+
+/// The elves of `input`, before their first round; both parts play the same rounds.
+pub fn simulation(input: &str) -> Option<Grove> {
+    Some(Grove::new(parse_elves(input)?))
+}
+
+impl Simulation for Grove {
+    type Snapshot = Grove;
+    const CONDITIONS: &'static str = "`elf x,y` (an elf moves there), `still` (no elf moves)";
+
+    fn step(&mut self) -> bool {
+        if self.rounds > 0 && self.moved_to.is_empty() {
+            return false;
+        }
+        self.round();
+        true
+    }
+
+    fn state(&self) -> String {
+        format!(
+            "after round {}: {} elves moved, {} empty ground tiles",
+            self.rounds,
+            self.moved_to.len(),
+            self.empty_ground().unwrap_or(0)
+        )
+    }
+
+    fn render(&self) -> String {
+        let Some((min_x, max_x)) = self.elves.iter().map(|e| e.0).minmax().into_option() else {
+            return String::new();
+        };
+        let (min_y, max_y) = self
+            .elves
+            .iter()
+            .map(|e| e.1)
+            .minmax()
+            .into_option()
+            .unwrap();
+        (min_y..=max_y)
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| match (x, y) {
+                        elf if self.moved_to.contains(&elf) => '@',
+                        elf if self.elves.contains(&elf) => '#',
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+
+    fn snapshot(&self) -> Grove {
+        self.clone()
+    }
+
+    fn restore(&mut self, snapshot: Grove) {
+        *self = snapshot;
+    }
+
+    fn holds(&self, condition: &str) -> Option<bool> {
+        match condition.split_once(' ') {
+            Some(("elf", at)) => {
+                let [x, y] = at.ints().ok()?;
+                Some(self.moved_to.contains(&(x, y)))
+            }
+            None if condition == "still" => Some(self.rounds > 0 && self.moved_to.is_empty()),
+            _ => None,
+        }
+    }
+}
+
+impl Checkpoint for Grove {
+    fn save(&self) -> Value {
+        json!({
+            "elves": self.elves,
+            "rounds": self.rounds,
+            "moved_to": self.moved_to,
+        })
+    }
+
+    fn load(&mut self, state: &Value) -> Option<()> {
+        let field = |name: &str| state.get(name).cloned();
+        let elves = serde_json::from_value(field("elves")?).ok()?;
+        let rounds = serde_json::from_value(field("rounds")?).ok()?;
+        let moved_to = serde_json::from_value(field("moved_to")?).ok()?;
+
+        // the directions turn once a round, so the rounds played say where they stand.
+        *self = Grove::new(elves);
+        self.moves.rotate_left(rounds % 4);
+        self.rounds = rounds;
+        self.moved_to = moved_to;
+        Some(())
+    }
+}
+
+/// The elves' positions, or `None` if the scan holds anything but `.` and `#`.
+pub fn parse_elves(input: &str) -> Option<HashSet<Position>> {
+    let mut elves = HashSet::new();
+    for (y, line) in input.lines().enumerate() {
+        for (x, c) in line.char_indices() {
+            match c {
+                '#' => {
+                    elves.insert((x as isize, y as isize));
+                }
+                '.' => {}
+                _ => return None,
+            }
+        }
+    }
+    Some(elves)
+}
+
+/// A `size` × `size` grove with an elf on roughly half of the squares.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    let size = size.max(1);
+    let mut grove = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| if rng.chance(1, 2) { '#' } else { '.' })
+                .collect_vec()
+        })
+        .collect_vec();
+    grove[size / 2][size / 2] = '#';
+    grove
+        .iter()
+        .map(|row| row.iter().collect::<String>())
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 23);
+        assert_eq!(part_one(&input, &Params::default()), Some(110));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 23);
+        assert_eq!(part_two(&input, &Params::default()), Some(20));
+    }
+}
@@ -0,0 +1,388 @@
+use std::collections::HashSet;
+
+use crate::checkpoint::{self, Checkpoint};
+use crate::helpers::{GetInts, Rng, Simulation};
+use crate::{Param, Params};
+use itertools::Itertools;
+use serde_json::{json, Value};
+
+pub type Position = (isize, isize);
+
+trait OOBAdd {
+    fn oob_add(&self, other: &Self, box_size: &(usize, usize)) -> Self;
+}
+
+impl OOBAdd for Position {
+    fn oob_add(&self, other: &Self, box_size: &(usize, usize)) -> Self {
+        let box_size = (box_size.0 as isize, box_size.1 as isize);
+        match (self.0 + other.0, self.1 + other.1) {
+            (0, y) => (box_size.0 - 2, y),
+            (x, y) if x == (box_size.0 - 1) => (1, y),
+            (x, 0) => (x, box_size.1 - 2),
+            (x, y) if y == (box_size.1 - 1) => (x, 1),
+            (x, y) => (x, y),
+        }
+    }
+}
+
+trait Neighbours {
+    fn valid_neighbours(
+        &self,
+        blizzards_coords: &HashSet<Position>,
+        box_size: &(usize, usize),
+        entrance: &Position,
+        exit: &Position,
+    ) -> Vec<Position>;
+    fn neighbours(&self) -> Vec<Position>;
+}
+
+impl Neighbours for Position {
+    fn neighbours(&self) -> Vec<Position> {
+        let deltas = vec![(-1, 0), (1, 0), (0, -1), (0, 1), (0, 0)];
+        deltas
+            .into_iter()
+            .map(|delta| (delta.0 + self.0, delta.1 + self.1))
+            .collect()
+    }
+
+    fn valid_neighbours(
+        &self,
+        blizzards_coords: &HashSet<Position>,
+        box_size: &(usize, usize),
+        entrance: &Position,
+        exit: &Position,
+    ) -> Vec<Position> {
+        self.neighbours()
+            .into_iter()
+            .filter(|n| {
+                (n == entrance)
+                    || (n == exit)
+                    || (!blizzards_coords.contains(n)
+                        && n.0 > 0
+                        && n.1 > 0
+                        && n.0 < (box_size.0 as isize) - 1
+                        && n.1 < (box_size.1 as isize) - 1)
+            })
+            .collect()
+    }
+}
+
+fn next_position(blizzard: &char, position: &Position, box_size: &(usize, usize)) -> Position {
+    match blizzard {
+        '<' => position.oob_add(&(-1, 0), box_size),
+        '>' => position.oob_add(&(1, 0), box_size),
+        '^' => position.oob_add(&(0, -1), box_size),
+        'v' => position.oob_add(&(0, 1), box_size),
+        _ => unimplemented!(),
+    }
+}
+
+fn next_blizzards(
+    blizzards: &HashSet<(Position, char)>,
+    box_size: &(usize, usize),
+) -> HashSet<(Position, char)> {
+    blizzards
+        .iter()
+        .map(|(coords, c)| (next_position(c, coords, box_size), *c))
+        .collect()
+}
+
+fn blizzards_coords(blizzards: &HashSet<(Position, char)>) -> HashSet<Position> {
+    blizzards.iter().map(|(coords, _)| *coords).collect()
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
+}
+
+/// The valley as scanned: its blizzards, its size including walls, and the two openings.
+#[derive(Debug, Clone)]
+pub struct Valley {
+    pub blizzards: HashSet<(Position, char)>,
+    pub box_size: (usize, usize),
+    pub entrance: Position,
+    pub exit: Position,
+}
+
+/// The valley, or `None` unless the input is a walled rectangle with an opening in its top and
+/// bottom walls and blizzards inside.
+pub fn parse_valley(input: &str) -> Option<Valley> {
+    let lines = input.lines().map(|line| line.trim()).collect_vec();
+    let box_size = (lines.first()?.len(), lines.len());
+    if box_size.0 < 3 || box_size.1 < 3 || lines.iter().any(|line| line.len() != box_size.0) {
+        return None;
+    }
+
+    let mut blizzards = HashSet::new();
+    for (y, line) in lines.iter().enumerate() {
+        for (x, c) in line.char_indices() {
+            let wall = x == 0 || y == 0 || x + 1 == box_size.0 || y + 1 == box_size.1;
+            match c {
+                '#' | '.' => {}
+                '<' | '>' | '^' | 'v' if !wall => {
+                    blizzards.insert(((x as isize, y as isize), c));
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    let opening = |y: usize| {
+        let x = lines[y].find('.')?;
+        (x > 0 && x + 1 < box_size.0).then_some((x as isize, y as isize))
+    };
+    Some(Valley {
+        blizzards,
+        box_size,
+        entrance: opening(0)?,
+        exit: opening(box_size.1 - 1)?,
+    })
+}
+
+/// Only the checkpoint params: the valley and trips come from the input.
+pub const PARAMS: &[Param] = &[checkpoint::EVERY, checkpoint::PATH, checkpoint::RESUME];
+
+pub fn part_one(input: &str, params: &Params) -> Option<usize> {
+    cross(simulation(input, 1)?, 1, params)
+}
+
+pub fn part_two(input: &str, params: &Params) -> Option<usize> {
+    cross(simulation(input, 2)?, 2, params)
+}
+
+/// Minutes until the last trip ends, or `None` if one of them cannot.
+fn cross(mut expedition: Expedition, part: u8, params: &Params) -> Option<usize> {
+    checkpoint::run(&mut expedition, 24, part, params, usize::MAX);
+    expedition.goals.is_empty().then_some(expedition.minute)
+}
+
+/// Everywhere the expedition can be, minute by minute, over its trips between the openings.
+#[derive(Debug, Clone)]
+pub struct Expedition {
+    blizzards: HashSet<(Position, char)>,
+    box_size: (usize, usize),
+    entrance: Position,
+    exit: Position,
+    /// The blizzards repeat with this period, and so does every state of the search.
+    period: usize,
+    pub minute: usize,
+    pub reachable: HashSet<Position>,
+    /// The openings still to reach, the current trip's first.
+    pub goals: Vec<Position>,
+    /// When the current trip began, and where it could reach one or more periods later.
+    trip_start: usize,
+    last_period: Option<Vec<Position>>,
+    pub stuck: bool,
+}
+
+impl Expedition {
+    /// The expedition at the entrance, to cross the valley `trips` times.
+    pub fn new(valley: Valley, trips: usize) -> Self {
+        let Valley {
+            blizzards,
+            box_size,
+            entrance,
+            exit,
+        } = valley;
+        Expedition {
+            blizzards,
+            box_size,
+            entrance,
+            exit,
+            period: lcm(box_size.0 - 2, box_size.1 - 2),
+            minute: 0,
+            reachable: HashSet::from([entrance]),
+            goals: (0..trips)
+                .map(|trip| if trip % 2 == 0 { exit } else { entrance })
+                .collect(),
+            trip_start: 0,
+            last_period: None,
+            stuck: false,
+        }
+    }
+
+    /// Advances a minute: the blizzards move, and so does the expedition, any way it can.
+    fn advance(&mut self) {
+        self.minute += 1;
+        self.blizzards = next_blizzards(&self.blizzards, &self.box_size);
+        let blizzards_coords = blizzards_coords(&self.blizzards);
+        self.reachable = self
+            .reachable
+            .iter()
+            .flat_map(|position| {
+                position.valid_neighbours(
+                    &blizzards_coords,
+                    &self.box_size,
+                    &self.entrance,
+                    &self.exit,
+                )
+            })
+            .collect();
+
+        if self.reachable.contains(&self.goals[0]) {
+            self.reachable = HashSet::from([self.goals.remove(0)]);
+            self.trip_start = self.minute;
+            self.last_period = None;
+        } else if (self.minute - self.trip_start).is_multiple_of(self.period) {
+            // waiting at an opening is always safe, so what can be reached a period later only
+            // grows; once it stops growing, the goal is out of reach.
+            let reachable = self.reachable.iter().copied().sorted().collect_vec();
+            self.stuck = self.last_period.as_ref() == Some(&reachable);
+            self.last_period = Some(reachable);
+        }
+    }
+}
+
+/// The valley of `input`, crossed once for part one and three times for part two.
+pub fn simulation(input: &str, part: u8) -> Option<Expedition> {
+    Some(Expedition::new(
+        parse_valley(input)?,
+        if part == 1 { 1 } else { 3 },
+    ))
+}
+
+impl Simulation for Expedition {
+    type Snapshot = Expedition;
+    const CONDITIONS: &'static str = "`reach x,y`, `minute N`";
+
+    fn step(&mut self) -> bool {
+        if self.goals.is_empty() || self.stuck {
+            return false;
+        }
+        self.advance();
+        true
+    }
+
+    fn state(&self) -> String {
+        format!(
+            "minute {}: {} positions reachable, {} trips to go{}",
+            self.minute,
+            self.reachable.len(),
+            self.goals.len(),
+            if self.stuck { ", stuck" } else { "" }
+        )
+    }
+
+    fn render(&self) -> String {
+        let blizzards = self
+            .blizzards
+            .iter()
+            .into_group_map_by(|(coords, _)| *coords);
+        (0..self.box_size.1 as isize)
+            .map(|y| {
+                (0..self.box_size.0 as isize)
+                    .map(|x| match blizzards.get(&(x, y)).map(|here| &here[..]) {
+                        _ if self.reachable.contains(&(x, y)) => 'E',
+                        Some([(_, blizzard)]) => *blizzard,
+                        Some(here) => char::from_digit(here.len().min(9) as u32, 10).unwrap(),
+                        None if (x, y) == self.entrance || (x, y) == self.exit => '.',
+                        None if x == 0
+                            || y == 0
+                            || x == self.box_size.0 as isize - 1
+                            || y == self.box_size.1 as isize - 1 =>
+                        {
+                            '#'
+                        }
+                        None => '.',
+                    })
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+
+    fn snapshot(&self) -> Expedition {
+        self.clone()
+    }
+
+    fn restore(&mut self, snapshot: Expedition) {
+        *self = snapshot;
+    }
+
+    fn holds(&self, condition: &str) -> Option<bool> {
+        match condition.split_once(' ')? {
+            ("reach", at) => {
+                let [x, y] = at.ints().ok()?;
+                Some(self.reachable.contains(&(x, y)))
+            }
+            ("minute", minute) => Some(self.minute == minute.parse::<usize>().ok()?),
+            _ => None,
+        }
+    }
+}
+
+impl Checkpoint for Expedition {
+    fn save(&self) -> Value {
+        json!({
+            "minute": self.minute,
+            "blizzards": self.blizzards,
+            "reachable": self.reachable,
+            "goals": self.goals,
+            "trip_start": self.trip_start,
+            "last_period": self.last_period,
+            "stuck": self.stuck,
+        })
+    }
+
+    fn load(&mut self, state: &Value) -> Option<()> {
+        let field = |name: &str| state.get(name).cloned();
+        let blizzards: HashSet<(Position, char)> =
+            serde_json::from_value(field("blizzards")?).ok()?;
+        // the same valley has as many blizzards, moved on.
+        if blizzards.len() != self.blizzards.len() {
+            return None;
+        }
+
+        self.minute = serde_json::from_value(field("minute")?).ok()?;
+        self.blizzards = blizzards;
+        self.reachable = serde_json::from_value(field("reachable")?).ok()?;
+        self.goals = serde_json::from_value(field("goals")?).ok()?;
+        self.trip_start = serde_json::from_value(field("trip_start")?).ok()?;
+        self.last_period = serde_json::from_value(field("last_period")?).ok()?;
+        self.stuck = serde_json::from_value(field("stuck")?).ok()?;
+        Some(())
+    }
+}
+
+/// A valley with a `size` × `size` interior, a blizzard on about a third of its squares and no
+/// vertical blizzards in the entrance or exit columns.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    let inner = size.max(3);
+    let mut lines = vec![format!("#.{}", "#".repeat(inner))];
+    for _ in 0..inner {
+        let row = (1..=inner)
+            .map(|x| match rng.chance(1, 3) {
+                false => '.',
+                true if x == 1 || x == inner => *rng.choose(&['<', '>']),
+                true => *rng.choose(&['<', '>', '^', 'v']),
+            })
+            .collect::<String>();
+        lines.push(format!("#{}#", row));
+    }
+    lines.push(format!("{}.#", "#".repeat(inner)));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 24);
+        assert_eq!(part_one(&input, &Params::default()), Some(18));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 24);
+        assert_eq!(part_two(&input, &Params::default()), Some(54));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::helpers::{BigInt, Rng};
+use crate::Params;
+use itertools::Itertools;
+
+/// The value of a SNAFU number, or `None` on a digit other than `=-012`.
+pub fn parse_snafu(input: &str) -> Option<BigInt> {
+    input.chars().try_fold(BigInt::zero(), |accum, char| {
+        let digit = match char {
+            '-' => -1,
+            '=' => -2,
+            '0'..='2' => char.to_digit(3)? as i128,
+            _ => return None,
+        };
+        Some(accum * BigInt::from(5) + BigInt::from(digit))
+    })
+}
+
+pub fn to_snafu(input: &BigInt) -> String {
+    if input.is_zero() {
+        return String::from("0");
+    }
+
+    let mut input = input.clone();
+    let mut result = vec![];
+    while !input.is_zero() {
+        let (quotient, digit) = input.div_rem_euclid(5);
+        let (digit, carry_over) = match digit {
+            3 => ('=', 1),
+            4 => ('-', 1),
+            x => (char::from_digit(x, 5).unwrap(), 0),
+        };
+        result.push(digit);
+        input = quotient + BigInt::from(carry_over);
+    }
+    result.into_iter().rev().collect()
+}
+
+pub fn part_one(input: &str) -> Option<String> {
+    let sum: BigInt = input.lines().map(parse_snafu).sum::<Option<_>>()?;
+    Some(to_snafu(&sum))
+}
+
+pub fn part_two(_input: &str) -> Option<usize> {
+    None
+}
+
+/// `size` random SNAFU numbers of up to twenty-one digits.
+pub fn generate(size: usize, rng: &mut Rng, _params: &Params) -> String {
+    (0..size.max(1))
+        .map(|_| to_snafu(&BigInt::from(rng.between(1, 10_isize.pow(14)))))
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 25);
+        assert_eq!(part_one(&input), Some(String::from("2=-1=0")));
+    }
+
+    #[test]
+    fn test_long_snafu() {
+        let long = "1=-0-2".repeat(20);
+        let sum = parse_snafu(&long).unwrap() + parse_snafu(&long).unwrap();
+        assert!(sum.to_i128().is_none());
+        assert_eq!(
+            to_snafu(&sum),
+            part_one(&format!("{}\n{}", long, long)).unwrap()
+        );
+        assert_eq!(parse_snafu(&to_snafu(&sum)), Some(sum));
+        assert_eq!(to_snafu(&parse_snafu("-=").unwrap()), "-=");
+        assert_eq!(parse_snafu("1=3"), None);
+        assert_eq!(parse_snafu("4-"), None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 25);
+        assert_eq!(part_two(&input), None);
+    }
+}
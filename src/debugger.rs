@@ -0,0 +1,258 @@
+/*
+ * Interactive stepping through a `Simulation`, e.g. `cargo solve 23 -- --debug --part 2`.
+ * A snapshot is kept every `SNAPSHOT_EVERY` steps, so rewinding restores the closest one before
+ * the target and replays the steps after it.
+ */
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+
+use crate::helpers::Simulation;
+
+const SNAPSHOT_EVERY: usize = 10;
+
+/// Steps a breakpoint runs for at most, in case its condition never holds.
+const MAX_RUN: usize = 1_000_000;
+
+const HELP: &str = "commands:
+  s [N]        step N times (1 by default, also on an empty line)
+  g N          go to step N, forwards or back
+  r [N]        rewind N steps (1 by default)
+  b CONDITION  step until CONDITION holds
+  p            print the current state
+  h            show this help
+  q            quit";
+
+/// The part to debug if `--debug` was passed: the one given by `--part`, 1 by default.
+pub fn requested() -> Option<u8> {
+    let mut args = pico_args::Arguments::from_env();
+    if !args.contains("--debug") {
+        return None;
+    }
+    let part = args
+        .opt_value_from_str("--part")
+        .expect("could not read --part argument");
+    Some(part.unwrap_or(1))
+}
+
+/// Debugs `simulation` on the terminal, or explains that there is nothing to debug.
+pub fn debug(simulation: Option<impl Simulation>) {
+    match simulation {
+        Some(simulation) => Debugger::new(simulation)
+            .run(io::stdin().lock(), io::stdout().lock())
+            .expect("could not talk to the terminal"),
+        None => eprintln!("The input cannot be simulated."),
+    }
+}
+
+pub struct Debugger<S: Simulation> {
+    simulation: S,
+    steps: usize,
+    finished: bool,
+    snapshots: BTreeMap<usize, S::Snapshot>,
+}
+
+impl<S: Simulation> Debugger<S> {
+    pub fn new(simulation: S) -> Self {
+        let snapshots = BTreeMap::from([(0, simulation.snapshot())]);
+        Debugger {
+            simulation,
+            steps: 0,
+            finished: false,
+            snapshots,
+        }
+    }
+
+    pub fn simulation(&self) -> &S {
+        &self.simulation
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Takes one step, or returns `false` if the simulation is over.
+    pub fn forward(&mut self) -> bool {
+        if self.finished || !self.simulation.step() {
+            self.finished = true;
+            return false;
+        }
+        self.steps += 1;
+        if self.steps.is_multiple_of(SNAPSHOT_EVERY) && !self.snapshots.contains_key(&self.steps) {
+            self.snapshots
+                .insert(self.steps, self.simulation.snapshot());
+        }
+        true
+    }
+
+    /// Goes to step `target`, or as close to it as the simulation gets.
+    pub fn go_to(&mut self, target: usize) {
+        if target < self.steps {
+            let (&steps, snapshot) = self.snapshots.range(..=target).next_back().unwrap();
+            self.simulation.restore(snapshot.clone());
+            self.steps = steps;
+            self.finished = false;
+        }
+        while self.steps < target && self.forward() {}
+    }
+
+    /// Steps until `condition` holds, for at most `limit` steps. Whether it held, or an error
+    /// if the simulation does not understand it.
+    pub fn run_until(&mut self, condition: &str, limit: usize) -> Result<bool, String> {
+        if self.simulation.holds(condition).is_none() {
+            return Err(format!(
+                "unknown condition `{}`, expected one of: {}",
+                condition,
+                S::CONDITIONS
+            ));
+        }
+        for _ in 0..limit {
+            if !self.forward() {
+                return Ok(false);
+            }
+            if self.simulation.holds(condition) == Some(true) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Reads commands from `commands` until it ends or says `q`, showing the state after each.
+    pub fn run(&mut self, commands: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        writeln!(output, "{}", HELP)?;
+        self.show(&mut output)?;
+        write!(output, "> ")?;
+        output.flush()?;
+
+        for command in commands.lines() {
+            let command = command?;
+            let (name, argument) = match command.trim().split_once(' ') {
+                Some((name, argument)) => (name, argument.trim()),
+                None => (command.trim(), ""),
+            };
+            let count = argument.parse::<usize>().ok();
+            match (name, count) {
+                ("" | "s", _) => self.go_to(self.steps + count.unwrap_or(1)),
+                ("g", Some(target)) => self.go_to(target),
+                ("r", _) => self.go_to(self.steps.saturating_sub(count.unwrap_or(1))),
+                ("b", _) if !argument.is_empty() => match self.run_until(argument, MAX_RUN) {
+                    Ok(true) => writeln!(output, "`{}` holds", argument)?,
+                    Ok(false) => writeln!(output, "`{}` never held", argument)?,
+                    Err(error) => writeln!(output, "{}", error)?,
+                },
+                ("p", _) => {}
+                ("q", _) => return Ok(()),
+                _ => {
+                    writeln!(output, "{}", HELP)?;
+                    write!(output, "> ")?;
+                    output.flush()?;
+                    continue;
+                }
+            }
+            self.show(&mut output)?;
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+        Ok(())
+    }
+
+    fn show(&self, output: &mut impl Write) -> io::Result<()> {
+        writeln!(output, "{}", self.simulation.render())?;
+        writeln!(
+            output,
+            "step {}{}: {}",
+            self.steps,
+            if self.finished { " (finished)" } else { "" },
+            self.simulation.state()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up to a limit, recording every value it has held.
+    #[derive(Clone)]
+    struct Counter {
+        value: usize,
+        limit: usize,
+        history: Vec<usize>,
+    }
+
+    impl Simulation for Counter {
+        type Snapshot = Counter;
+        const CONDITIONS: &'static str = "value N";
+
+        fn step(&mut self) -> bool {
+            if self.value == self.limit {
+                return false;
+            }
+            self.value += 1;
+            self.history.push(self.value);
+            true
+        }
+
+        fn state(&self) -> String {
+            format!("value {}", self.value)
+        }
+
+        fn render(&self) -> String {
+            "#".repeat(self.value)
+        }
+
+        fn snapshot(&self) -> Counter {
+            self.clone()
+        }
+
+        fn restore(&mut self, snapshot: Counter) {
+            *self = snapshot;
+        }
+
+        fn holds(&self, condition: &str) -> Option<bool> {
+            let value = condition.strip_prefix("value ")?.parse::<usize>().ok()?;
+            Some(self.value == value)
+        }
+    }
+
+    #[test]
+    fn test_debugger() {
+        let mut debugger = Debugger::new(Counter {
+            value: 0,
+            limit: 25,
+            history: vec![],
+        });
+        debugger.go_to(17);
+        debugger.go_to(4);
+        assert_eq!(debugger.simulation().value, 4);
+        assert_eq!(debugger.simulation().history, vec![1, 2, 3, 4]);
+
+        assert_eq!(debugger.run_until("value 12", MAX_RUN), Ok(true));
+        assert_eq!(debugger.steps(), 12);
+        assert!(debugger.run_until("value", MAX_RUN).is_err());
+        assert_eq!(debugger.run_until("value 3", MAX_RUN), Ok(false));
+        assert_eq!(debugger.steps(), 25);
+
+        let mut output = vec![];
+        debugger
+            .run("r 5\n\ng 100\nq\ns\n".as_bytes(), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let states = output
+            .lines()
+            .filter_map(|line| {
+                line.strip_prefix("> ")
+                    .unwrap_or(line)
+                    .strip_prefix("step ")
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            states,
+            vec![
+                "25 (finished): value 25",
+                "20: value 20",
+                "21: value 21",
+                "25 (finished): value 25"
+            ]
+        );
+    }
+}
@@ -0,0 +1,214 @@
+/*
+ * Differential testing: a solver is compared with a brute-force reference on generated inputs.
+ * A disagreement is shrunk to a minimal counter-example, saved as
+ * `src/examples/NN-regression-K.txt` and checked on every later run.
+ */
+use std::cell::Cell;
+use std::env;
+use std::fmt::Debug;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use crate::helpers::Rng;
+use crate::panic_message;
+
+/// Compares `solver` with `reference` on every saved regression input of `day`, then on
+/// `cases` inputs from `generate`, seeded `0..cases`. The reference is the source of truth: an
+/// input it rejects (by panicking) is skipped. Panics on the first disagreement, after saving
+/// the shrunk input.
+pub fn check<T: PartialEq + Debug>(
+    day: u8,
+    cases: u64,
+    generate: impl Fn(&mut Rng) -> String,
+    solver: impl Fn(&str) -> Option<T>,
+    reference: impl Fn(&str) -> Option<T>,
+) {
+    let disagreement = |input: &str| -> Option<String> {
+        let expected = panic::catch_unwind(AssertUnwindSafe(|| reference(input))).ok()?;
+        match panic::catch_unwind(AssertUnwindSafe(|| solver(input))) {
+            Ok(actual) if actual == expected => None,
+            Ok(actual) => Some(format!("expected {:?}, got {:?}", expected, actual)),
+            Err(panic) => Some(format!(
+                "expected {:?}, panicked with `{}`",
+                expected,
+                panic_message(&*panic)
+            )),
+        }
+    };
+
+    for (path, input) in regressions(day) {
+        if let Some(message) = disagreement(&input) {
+            panic!("day {} regression {}: {}", day, path, message);
+        }
+    }
+
+    for seed in 0..cases {
+        let input = generate(&mut Rng::new(seed));
+        if disagreement(&input).is_none() {
+            continue;
+        }
+        // the shrinker triggers many expected panics, so keep them off the output.
+        let input = {
+            let _quiet = Quiet::new();
+            shrink(&input, |candidate| disagreement(candidate).is_some())
+        };
+
+        let path = save_input(day, "regression", &input);
+        panic!(
+            "day {}, seed {}: {}; minimal input saved to {}:\n{}",
+            day,
+            seed,
+            disagreement(&input).unwrap(),
+            path,
+            input
+        );
+    }
+}
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Keeps the panics of the current thread off the output until dropped, unwinding included.
+/// Other threads, e.g. tests running alongside, still report theirs.
+struct Quiet;
+
+impl Quiet {
+    fn new() -> Quiet {
+        // the panic hook is process-wide, so it is only ever replaced once, by one that asks
+        // the panicking thread whether to pass the panic on.
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| {
+            let hook = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                if !QUIET.with(Cell::get) {
+                    hook(info);
+                }
+            }));
+        });
+        QUIET.with(|quiet| quiet.set(true));
+        Quiet
+    }
+}
+
+impl Drop for Quiet {
+    fn drop(&mut self) {
+        QUIET.with(|quiet| quiet.set(false));
+    }
+}
+
+fn examples_folder() -> std::path::PathBuf {
+    env::current_dir().unwrap().join("src").join("examples")
+}
+
+/// Saved regression inputs of `day`, with their paths.
+pub fn regressions(day: u8) -> Vec<(String, String)> {
+    saved_inputs(day, "regression")
+}
+
+/// Inputs saved as `src/examples/NN-<kind>-K.txt`, with their paths.
+pub fn saved_inputs(day: u8, kind: &str) -> Vec<(String, String)> {
+    let prefix = format!("{:02}-{}-", day, kind);
+    let mut files = fs::read_dir(examples_folder())
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".txt"))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+        .into_iter()
+        .map(|path| {
+            let input = fs::read_to_string(&path).expect("could not open saved input");
+            (path.display().to_string(), input)
+        })
+        .collect()
+}
+
+/// Saves `input` under the first free `src/examples/NN-<kind>-K.txt` and returns its path.
+pub fn save_input(day: u8, kind: &str, input: &str) -> String {
+    let folder = examples_folder();
+    let path = (1..)
+        .map(|idx| folder.join(format!("{:02}-{}-{}.txt", day, kind, idx)))
+        .find(|path| !path.exists())
+        .unwrap();
+    fs::write(&path, input).expect("could not write saved input");
+    path.display().to_string()
+}
+
+/// Removes ever smaller runs of lines (or of characters, for a single-line input) for as long
+/// as the input keeps failing.
+pub fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let (mut units, separator): (Vec<&str>, &str) = match input.trim_end().contains('\n') {
+        true => (input.lines().collect(), "\n"),
+        false => (
+            input
+                .char_indices()
+                .map(|(idx, c)| &input[idx..idx + c.len_utf8()])
+                .collect(),
+            "",
+        ),
+    };
+
+    let mut chunk = (units.len() / 2).max(1);
+    loop {
+        let mut removed = false;
+        let mut start = 0;
+        while start < units.len() {
+            let end = (start + chunk).min(units.len());
+            let candidate = [&units[..start], &units[end..]].concat();
+            if !candidate.is_empty() && fails(&candidate.join(separator)) {
+                units = candidate;
+                removed = true;
+            } else {
+                start += chunk;
+            }
+        }
+        if !removed {
+            if chunk == 1 {
+                break;
+            }
+            chunk /= 2;
+        }
+    }
+    units.join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shrink() {
+        let input = (1..=20)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let fails = |input: &str| input.lines().any(|line| line == "7") && input.contains("13");
+        assert_eq!(shrink(&input, fails), "7\n13");
+
+        assert_eq!(shrink("<<><>>><", |input| input.contains("><>")), "><>");
+    }
+
+    #[test]
+    fn test_quiet() {
+        let unwound = panic::catch_unwind(|| {
+            let _quiet = Quiet::new();
+            assert!(QUIET.with(Cell::get));
+            panic!("expected");
+        });
+        assert!(unwound.is_err());
+        assert!(!QUIET.with(Cell::get));
+        // other threads were never quiet.
+        let other = std::thread::spawn(|| QUIET.with(Cell::get));
+        assert!(!other.join().unwrap());
+    }
+}
@@ -0,0 +1,206 @@
+/*
+ * Offline fuzzing of every day's parser and solvers, e.g. `cargo fuzz 13 --runs 50000`.
+ * The seed corpus is the day's checked-in `src/examples`; mutated inputs that make a solver
+ * panic or hang are shrunk and saved as `src/examples/NN-crash-K.txt`, which `test_crashes`
+ * replays on every run. A solver should answer `None` to input it cannot make sense of.
+ */
+use std::env;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+
+use crate::differential::{saved_inputs, shrink};
+use crate::generators::Day;
+use crate::helpers::Rng;
+use crate::{panic_message, Params};
+
+/// Where the last panic happened, recorded by the hook from `install_hook`.
+static LOCATION: Mutex<Option<String>> = Mutex::new(None);
+
+/// Params that keep the expensive days fast, as in the generator tests, for those of them
+/// `day` reads.
+pub fn params(day: &Day) -> Params {
+    [("limit", 20), ("row", 10)]
+        .into_iter()
+        .filter(|(key, _)| day.params.iter().any(|param| param.key == *key))
+        .fold(Params::default(), |params, (key, value)| {
+            params.with(key, value)
+        })
+}
+
+/// The day's example and every input saved for it since.
+pub fn corpus(day: u8) -> Vec<String> {
+    let example = env::current_dir()
+        .unwrap()
+        .join("src")
+        .join("examples")
+        .join(format!("{:02}.txt", day));
+    let mut corpus = fs::read_to_string(example).into_iter().collect::<Vec<_>>();
+    for kind in ["regression", "crash"] {
+        corpus.extend(saved_inputs(day, kind).into_iter().map(|(_, input)| input));
+    }
+    corpus
+}
+
+/// Replaces the panic hook with one that only records where the panic happened, so the many
+/// expected panics of a fuzzing run stay off the output.
+pub fn install_hook() {
+    panic::set_hook(Box::new(|info| {
+        *LOCATION.lock().unwrap() = info.location().map(|location| location.to_string());
+    }));
+}
+
+/// Runs both parts of `day` on `input`. A panic is returned as its message and location.
+pub fn run(day: &Day, input: &str, params: &Params) -> Result<(), String> {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        (day.part_one)(input, params);
+        (day.part_two)(input, params);
+    }))
+    .map_err(|panic| {
+        let location = LOCATION.lock().unwrap().take();
+        format!(
+            "`{}` at {}",
+            panic_message(&*panic),
+            location.as_deref().unwrap_or("unknown location")
+        )
+    })
+}
+
+/// Shrinks a crashing `input` for as long as `crashes` reports the same place as `crash`.
+pub fn minimise(input: &str, crash: &str, crashes: impl Fn(&str) -> Option<String>) -> String {
+    let site = |crash: &str| crash.rsplit(" at ").next().unwrap_or_default().to_string();
+    shrink(input, |candidate| {
+        crashes(candidate).is_some_and(|other| site(&other) == site(crash))
+    })
+}
+
+/// Values that tend to break number handling.
+const NUMBERS: [&str; 8] = ["0", "1", "-1", "2", "9", "10", "99", "255"];
+
+/// Characters to insert, besides those already in the input.
+const EXTRA: &str = " \n-+,.:=0123456789abcxyz#()[]";
+
+/// One to four random edits of `input`: lines removed, duplicated, swapped or spliced in
+/// from `corpus`, characters inserted, replaced or removed, numbers replaced, or a truncation.
+pub fn mutate(input: &str, corpus: &[String], rng: &mut Rng) -> String {
+    let mut input = input.to_string();
+    for _ in 0..1 + rng.below(4) {
+        input = mutate_once(&input, corpus, rng);
+    }
+    input
+}
+
+fn mutate_once(input: &str, corpus: &[String], rng: &mut Rng) -> String {
+    let mut lines = input.lines().map(str::to_string).collect::<Vec<_>>();
+    let mut chars = input.chars().collect::<Vec<_>>();
+    if lines.is_empty() || chars.is_empty() {
+        return rng.choose(corpus).clone();
+    }
+    let alphabet = chars
+        .iter()
+        .copied()
+        .chain(EXTRA.chars())
+        .collect::<Vec<_>>();
+    let line = rng.below(lines.len());
+    let at = rng.below(chars.len());
+
+    match rng.below(9) {
+        0 => {
+            lines.remove(line);
+        }
+        1 => lines.insert(line, lines[line].clone()),
+        2 => {
+            let other = rng.below(lines.len());
+            lines.swap(line, other);
+        }
+        3 => {
+            let donor = rng.choose(corpus);
+            if let Some(spliced) = donor.lines().nth(rng.below(donor.lines().count().max(1))) {
+                lines.insert(line, spliced.to_string());
+            }
+        }
+        4 => chars.insert(at, *rng.choose(&alphabet)),
+        5 => chars[at] = *rng.choose(&alphabet),
+        6 => {
+            let end = (at + 1 + rng.below(4)).min(chars.len());
+            chars.drain(at..end);
+        }
+        7 => return replace_number(input, rng).unwrap_or_else(|| input.to_string()),
+        _ => chars.truncate(at),
+    }
+    match rng.below(9) {
+        0..=3 => lines.join("\n"),
+        _ => chars.into_iter().collect(),
+    }
+}
+
+/// Replaces a random run of digits with one of `NUMBERS` or a value near the old one.
+fn replace_number(input: &str, rng: &mut Rng) -> Option<String> {
+    let mut runs = vec![];
+    let mut start = None;
+    for (idx, c) in input.char_indices().chain([(input.len(), ' ')]) {
+        match (c.is_ascii_digit(), start) {
+            (true, None) => start = Some(idx),
+            (false, Some(from)) => {
+                runs.push(from..idx);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if runs.is_empty() {
+        return None;
+    }
+    let run = rng.choose(&runs).clone();
+    let value = match rng.chance(1, 2) {
+        true => rng.choose(&NUMBERS).to_string(),
+        false => {
+            let old = input[run.clone()].parse::<i64>().unwrap_or(0);
+            (old + rng.between(-3, 3) as i64).to_string()
+        }
+    };
+    Some(format!(
+        "{}{}{}",
+        &input[..run.start],
+        value,
+        &input[run.end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators;
+
+    #[test]
+    fn test_mutate() {
+        let corpus = vec!["1,2\n3,4".to_string()];
+        let mutated = (0..50)
+            .map(|seed| mutate(&corpus[0], &corpus, &mut Rng::new(seed)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            mutated,
+            (0..50)
+                .map(|seed| mutate(&corpus[0], &corpus, &mut Rng::new(seed)))
+                .collect::<Vec<_>>()
+        );
+        assert!(mutated.iter().any(|input| input != &corpus[0]));
+    }
+
+    #[test]
+    fn test_crashes() {
+        for number in 1..=25 {
+            let Some(day) = generators::day(number) else {
+                continue;
+            };
+            let params = params(&day);
+            for (path, input) in saved_inputs(number, "crash") {
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    (day.part_one)(&input, &params);
+                    (day.part_two)(&input, &params);
+                }));
+                assert!(result.is_ok(), "day {} crashes on {}", number, path);
+            }
+        }
+    }
+}
@@ -0,0 +1,348 @@
+/*
+ * Random puzzle inputs for stress and scaling tests, e.g. `cargo gen 12 --size 100 --seed 7`.
+ * Each day's `generate` reads the same params as its solvers, so inputs can be sized to them.
+ */
+use std::io::BufRead;
+
+use crate::helpers::Rng;
+use crate::{Param, Params};
+
+/// One part's solver, with its answer rendered as text.
+pub type Solver = fn(&str, &Params) -> Option<String>;
+
+/// One part's solver over a reader, for the days that can stream their input.
+pub type StreamSolver = fn(&mut dyn BufRead) -> Option<String>;
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub generate: fn(usize, &mut Rng, &Params) -> String,
+    /// The params its generator and solvers read.
+    pub params: &'static [Param],
+    pub part_one: Solver,
+    pub part_two: Solver,
+}
+
+// unused when every day that would use it is disabled.
+#[allow(unused_macros)]
+macro_rules! solver {
+    ($solver:path) => {
+        |input: &str, _: &Params| $solver(input).map(|result| result.to_string())
+    };
+    ($solver:path, params) => {
+        |input: &str, params: &Params| $solver(input, params).map(|result| result.to_string())
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! stream_solver {
+    ($solver:path) => {
+        |input: &mut dyn BufRead| $solver(input).map(|result| result.to_string())
+    };
+}
+
+/// Streaming solvers of both parts of `day`, if it has them and its feature is enabled.
+pub fn streaming(day: u8) -> Option<[StreamSolver; 2]> {
+    match day {
+        #[cfg(feature = "day01")]
+        1 => Some([
+            stream_solver!(crate::days::day01::part_one_stream),
+            stream_solver!(crate::days::day01::part_two_stream),
+        ]),
+        #[cfg(feature = "day02")]
+        2 => Some([
+            stream_solver!(crate::days::day02::part_one_stream),
+            stream_solver!(crate::days::day02::part_two_stream),
+        ]),
+        #[cfg(feature = "day03")]
+        3 => Some([
+            stream_solver!(crate::days::day03::part_one_stream),
+            stream_solver!(crate::days::day03::part_two_stream),
+        ]),
+        #[cfg(feature = "day04")]
+        4 => Some([
+            stream_solver!(crate::days::day04::part_one_stream),
+            stream_solver!(crate::days::day04::part_two_stream),
+        ]),
+        #[cfg(feature = "day06")]
+        6 => Some([
+            stream_solver!(crate::days::day06::part_one_stream),
+            stream_solver!(crate::days::day06::part_two_stream),
+        ]),
+        #[cfg(feature = "day09")]
+        9 => Some([
+            stream_solver!(crate::days::day09::part_one_stream),
+            stream_solver!(crate::days::day09::part_two_stream),
+        ]),
+        #[cfg(feature = "day10")]
+        10 => Some([
+            stream_solver!(crate::days::day10::part_one_stream),
+            stream_solver!(crate::days::day10::part_two_stream),
+        ]),
+        _ => None,
+    }
+}
+
+/// Generator and solvers for `day`, if its feature is enabled.
+pub fn day(day: u8) -> Option<Day> {
+    match day {
+        #[cfg(feature = "day01")]
+        1 => Some(Day {
+            generate: crate::days::day01::generate,
+            params: &[],
+            part_one: solver!(crate::days::day01::part_one),
+            part_two: solver!(crate::days::day01::part_two),
+        }),
+        #[cfg(feature = "day02")]
+        2 => Some(Day {
+            generate: crate::days::day02::generate,
+            params: &[],
+            part_one: solver!(crate::days::day02::part_one),
+            part_two: solver!(crate::days::day02::part_two),
+        }),
+        #[cfg(feature = "day03")]
+        3 => Some(Day {
+            generate: crate::days::day03::generate,
+            params: &[],
+            part_one: solver!(crate::days::day03::part_one),
+            part_two: solver!(crate::days::day03::part_two),
+        }),
+        #[cfg(feature = "day04")]
+        4 => Some(Day {
+            generate: crate::days::day04::generate,
+            params: &[],
+            part_one: solver!(crate::days::day04::part_one),
+            part_two: solver!(crate::days::day04::part_two),
+        }),
+        #[cfg(feature = "day05")]
+        5 => Some(Day {
+            generate: crate::days::day05::generate,
+            params: crate::days::day05::PARAMS,
+            part_one: solver!(crate::days::day05::part_one),
+            part_two: solver!(crate::days::day05::part_two),
+        }),
+        #[cfg(feature = "day06")]
+        6 => Some(Day {
+            generate: crate::days::day06::generate,
+            params: &[],
+            part_one: solver!(crate::days::day06::part_one),
+            part_two: solver!(crate::days::day06::part_two),
+        }),
+        #[cfg(feature = "day07")]
+        7 => Some(Day {
+            generate: crate::days::day07::generate,
+            params: crate::days::day07::PARAMS,
+            part_one: solver!(crate::days::day07::part_one, params),
+            part_two: solver!(crate::days::day07::part_two, params),
+        }),
+        #[cfg(feature = "day08")]
+        8 => Some(Day {
+            generate: crate::days::day08::generate,
+            params: crate::days::day08::PARAMS,
+            part_one: solver!(crate::days::day08::part_one),
+            part_two: solver!(crate::days::day08::part_two),
+        }),
+        #[cfg(feature = "day09")]
+        9 => Some(Day {
+            generate: crate::days::day09::generate,
+            params: crate::days::day09::PARAMS,
+            part_one: solver!(crate::days::day09::part_one),
+            part_two: solver!(crate::days::day09::part_two),
+        }),
+        #[cfg(feature = "day10")]
+        10 => Some(Day {
+            generate: crate::days::day10::generate,
+            params: crate::days::day10::PARAMS,
+            part_one: solver!(crate::days::day10::part_one),
+            part_two: solver!(crate::days::day10::part_two),
+        }),
+        #[cfg(feature = "day11")]
+        11 => Some(Day {
+            generate: crate::days::day11::generate,
+            params: crate::days::day11::PARAMS,
+            part_one: solver!(crate::days::day11::part_one, params),
+            part_two: solver!(crate::days::day11::part_two, params),
+        }),
+        #[cfg(feature = "day12")]
+        12 => Some(Day {
+            generate: crate::days::day12::generate,
+            params: &[],
+            part_one: solver!(crate::days::day12::part_one),
+            part_two: solver!(crate::days::day12::part_two),
+        }),
+        #[cfg(feature = "day13")]
+        13 => Some(Day {
+            generate: crate::days::day13::generate,
+            params: &[],
+            part_one: solver!(crate::days::day13::part_one),
+            part_two: solver!(crate::days::day13::part_two),
+        }),
+        #[cfg(feature = "day14")]
+        14 => Some(Day {
+            generate: crate::days::day14::generate,
+            params: crate::days::day14::PARAMS,
+            part_one: solver!(crate::days::day14::part_one),
+            part_two: solver!(crate::days::day14::part_two, params),
+        }),
+        #[cfg(feature = "day15")]
+        15 => Some(Day {
+            generate: crate::days::day15::generate,
+            params: crate::days::day15::PARAMS,
+            part_one: solver!(crate::days::day15::part_one, params),
+            part_two: solver!(crate::days::day15::part_two, params),
+        }),
+        #[cfg(feature = "day16")]
+        16 => Some(Day {
+            generate: crate::days::day16::generate,
+            params: crate::days::day16::PARAMS,
+            part_one: solver!(crate::days::day16::part_one, params),
+            part_two: solver!(crate::days::day16::part_two, params),
+        }),
+        #[cfg(feature = "day17")]
+        17 => Some(Day {
+            generate: crate::days::day17::generate,
+            params: crate::days::day17::PARAMS,
+            part_one: solver!(crate::days::day17::part_one, params),
+            part_two: solver!(crate::days::day17::part_two, params),
+        }),
+        #[cfg(feature = "day18")]
+        18 => Some(Day {
+            generate: crate::days::day18::generate,
+            params: &[],
+            part_one: solver!(crate::days::day18::part_one),
+            part_two: solver!(crate::days::day18::part_two),
+        }),
+        #[cfg(feature = "day19")]
+        19 => Some(Day {
+            generate: crate::days::day19::generate,
+            params: crate::days::day19::PARAMS,
+            part_one: solver!(crate::days::day19::part_one, params),
+            part_two: solver!(crate::days::day19::part_two, params),
+        }),
+        #[cfg(feature = "day20")]
+        20 => Some(Day {
+            generate: crate::days::day20::generate,
+            params: crate::days::day20::PARAMS,
+            part_one: solver!(crate::days::day20::part_one, params),
+            part_two: solver!(crate::days::day20::part_two, params),
+        }),
+        #[cfg(feature = "day21")]
+        21 => Some(Day {
+            generate: crate::days::day21::generate,
+            params: &[],
+            part_one: solver!(crate::days::day21::part_one),
+            part_two: solver!(crate::days::day21::part_two),
+        }),
+        #[cfg(feature = "day22")]
+        22 => Some(Day {
+            generate: crate::days::day22::generate,
+            params: &[],
+            part_one: solver!(crate::days::day22::part_one),
+            part_two: solver!(crate::days::day22::part_two),
+        }),
+        #[cfg(feature = "day23")]
+        23 => Some(Day {
+            generate: crate::days::day23::generate,
+            params: crate::days::day23::PARAMS,
+            part_one: solver!(crate::days::day23::part_one, params),
+            part_two: solver!(crate::days::day23::part_two, params),
+        }),
+        #[cfg(feature = "day24")]
+        24 => Some(Day {
+            generate: crate::days::day24::generate,
+            params: crate::days::day24::PARAMS,
+            part_one: solver!(crate::days::day24::part_one, params),
+            part_two: solver!(crate::days::day24::part_two, params),
+        }),
+        #[cfg(feature = "day25")]
+        25 => Some(Day {
+            generate: crate::days::day25::generate,
+            params: &[],
+            part_one: solver!(crate::days::day25::part_one),
+            part_two: solver!(crate::days::day25::part_two),
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generators() {
+        for number in 1..=25 {
+            let Some(day) = day(number) else {
+                continue;
+            };
+            // every default parses, and the expensive days are kept small for a debug build.
+            let defaults = day.params.iter().fold(Params::default(), |params, param| {
+                params.with(param.key, param.default)
+            });
+            assert_eq!(defaults.check(day.params), Ok(()), "day {}", number);
+            let params = crate::fuzz::params(&day);
+            for seed in 0..3 {
+                for size in [1, 6] {
+                    let input = (day.generate)(size, &mut Rng::new(seed), &params);
+                    assert_eq!(
+                        input,
+                        (day.generate)(size, &mut Rng::new(seed), &params),
+                        "day {} is not deterministic",
+                        number
+                    );
+                    assert!(
+                        (day.part_one)(&input, &params).is_some(),
+                        "day {} part one, seed {}, size {}:\n{}",
+                        number,
+                        seed,
+                        size,
+                        input
+                    );
+                    // day 25 has no second part.
+                    assert!(
+                        number == 25 || (day.part_two)(&input, &params).is_some(),
+                        "day {} part two, seed {}, size {}:\n{}",
+                        number,
+                        seed,
+                        size,
+                        input
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming() {
+        let params = Params::default();
+        for number in 1..=25 {
+            let (Some(day), Some(streaming)) = (day(number), streaming(number)) else {
+                continue;
+            };
+            // the example and saved inputs cover the malformed cases, generated ones the rest.
+            let mut inputs = crate::fuzz::corpus(number);
+            for seed in 0..5 {
+                inputs.push((day.generate)(20, &mut Rng::new(seed), &params));
+            }
+            inputs.push(inputs[0].replace('\n', "\r\n"));
+
+            for input in inputs {
+                for (part, (solver, stream)) in [day.part_one, day.part_two]
+                    .into_iter()
+                    .zip(streaming)
+                    .enumerate()
+                {
+                    assert_eq!(
+                        solver(&input, &params),
+                        stream(&mut input.as_bytes()),
+                        "day {} part {} streams a different answer for:\n{}",
+                        number,
+                        part + 1,
+                        input
+                    );
+                }
+            }
+            // input that is not UTF-8 is no answer, not a panic.
+            assert_eq!(streaming[0](&mut &b"\xff\xfe"[..]), None, "day {}", number);
+        }
+    }
+}
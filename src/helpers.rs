@@ -3,16 +3,27 @@
  * Example import from this file: `use advent_of_code::helpers::example_fn;`.
  */
 
-use std::{collections::HashSet, ops::RangeInclusive, str::FromStr};
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    fmt,
+    io::BufRead,
+    iter::Sum,
+    marker::PhantomData,
+    ops::{Add, Mul, Neg, RangeInclusive, Sub},
+    str::FromStr,
+};
 
 use itertools::Itertools;
 
+pub mod ocr;
+
 pub trait First<T> {
     fn first(&self) -> Option<&T>;
 }
 impl<T> First<T> for HashSet<T> {
     fn first(&self) -> Option<&T> {
-        if self.len() > 0 {
+        if !self.is_empty() {
             return self.iter().next();
         }
         None
@@ -80,3 +91,802 @@ impl<T: FromStr> GetNumbers<T> for str {
             .collect_vec()
     }
 }
+
+/// Integer types that [`Ints`] can decode directly from bytes.
+pub trait Integer: Copy {
+    const SIGNED: bool;
+
+    fn zero() -> Self;
+    /// Appends a decimal digit, moving away from zero in the direction of the sign.
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($signed:expr => $($t:ty),*) => {$(
+        impl Integer for $t {
+            const SIGNED: bool = $signed;
+
+            fn zero() -> Self {
+                0
+            }
+
+            fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                let shifted = self.checked_mul(10)?;
+                if negative {
+                    shifted.checked_sub(digit as $t)
+                } else {
+                    shifted.checked_add(digit as $t)
+                }
+            }
+        }
+    )*};
+}
+
+impl_integer!(true => i8, i16, i32, i64, i128, isize);
+impl_integer!(false => u8, u16, u32, u64, u128, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntsError {
+    Count { expected: usize, found: usize },
+    Overflow { offset: usize },
+}
+
+impl fmt::Display for IntsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntsError::Count { expected, found } => {
+                write!(f, "expected {} integers, found {}", expected, found)
+            }
+            IntsError::Overflow { offset } => {
+                write!(f, "integer at byte {} does not fit the target type", offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for IntsError {}
+
+/// Iterator over every integer embedded in a byte string, whatever separates them.
+///
+/// A `-` is read as a sign only for signed targets, when it is directly followed by a digit
+/// and not directly preceded by one, so `x=-3` yields `-3` while `2-4` yields `2` and `4`.
+/// An integer that does not fit `T` yields an [`IntsError::Overflow`], and the ones after it
+/// are still read.
+#[derive(Debug, Clone)]
+pub struct Ints<'a, T> {
+    bytes: &'a [u8],
+    position: usize,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T: Integer> Ints<'a, T> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Ints {
+            bytes,
+            position: 0,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Integer> Iterator for Ints<'a, T> {
+    type Item = Result<T, IntsError>;
+
+    fn next(&mut self) -> Option<Result<T, IntsError>> {
+        let bytes = self.bytes;
+        let start = (self.position..bytes.len()).find(|&idx| {
+            bytes[idx].is_ascii_digit()
+                || (T::SIGNED
+                    && bytes[idx] == b'-'
+                    && bytes.get(idx + 1).is_some_and(u8::is_ascii_digit)
+                    && (idx == 0 || !bytes[idx - 1].is_ascii_digit()))
+        });
+        let Some(start) = start else {
+            self.position = bytes.len();
+            return None;
+        };
+
+        let negative = bytes[start] == b'-';
+        let mut end = if negative { start + 1 } else { start };
+        let mut value = Some(T::zero());
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            value = value.and_then(|v| v.push_digit(bytes[end] - b'0', negative));
+            end += 1;
+        }
+        self.position = end;
+
+        Some(value.ok_or(IntsError::Overflow { offset: start }))
+    }
+}
+
+pub trait GetInts<T: Integer> {
+    fn ints_iter(&self) -> Ints<'_, T>;
+
+    /// Extracts exactly `N` integers, e.g. `let [x, y] = line.ints::<2>()?;`.
+    fn ints<const N: usize>(&self) -> Result<[T; N], IntsError> {
+        let mut ints = self.ints_iter();
+        let mut values = [T::zero(); N];
+        for (found, value) in values.iter_mut().enumerate() {
+            match ints.next() {
+                Some(next) => *value = next?,
+                None => return Err(IntsError::Count { expected: N, found }),
+            }
+        }
+
+        let extra = ints.count();
+        if extra > 0 {
+            return Err(IntsError::Count {
+                expected: N,
+                found: N + extra,
+            });
+        }
+        Ok(values)
+    }
+}
+
+impl<T: Integer> GetInts<T> for [u8] {
+    fn ints_iter(&self) -> Ints<'_, T> {
+        Ints::new(self)
+    }
+}
+
+impl<T: Integer> GetInts<T> for str {
+    fn ints_iter(&self) -> Ints<'_, T> {
+        Ints::new(self.as_bytes())
+    }
+}
+
+/// Signed integer that stays on `i128` while it fits and spills into base-2³² limbs beyond that.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigInt(Repr);
+
+/// Kept private, so every value has exactly one representation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Repr {
+    Small(i128),
+    /// Only ever holds values outside the `i128` range, with no leading zero limbs.
+    Large {
+        negative: bool,
+        magnitude: Vec<u32>,
+    },
+}
+
+impl BigInt {
+    pub fn zero() -> BigInt {
+        BigInt(Repr::Small(0))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == Repr::Small(0)
+    }
+
+    pub fn is_negative(&self) -> bool {
+        match &self.0 {
+            Repr::Small(value) => *value < 0,
+            Repr::Large { negative, .. } => *negative,
+        }
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        match &self.0 {
+            Repr::Small(value) => Some(*value),
+            Repr::Large { .. } => None,
+        }
+    }
+
+    /// Euclidean division by a small divisor, so the remainder is always in `0..divisor`.
+    pub fn div_rem_euclid(&self, divisor: u32) -> (BigInt, u32) {
+        match &self.0 {
+            Repr::Small(value) => (
+                BigInt::from(value.div_euclid(divisor as i128)),
+                value.rem_euclid(divisor as i128) as u32,
+            ),
+            Repr::Large {
+                negative,
+                magnitude,
+            } => {
+                let (quotient, remainder) = div_rem_magnitude(magnitude, divisor);
+                let quotient = BigInt::from_parts(*negative, quotient);
+                match (*negative, remainder) {
+                    (true, 1..) => (quotient - BigInt::from(1), divisor - remainder),
+                    _ => (quotient, remainder),
+                }
+            }
+        }
+    }
+
+    fn parts(&self) -> (bool, Vec<u32>) {
+        match &self.0 {
+            Repr::Small(value) => {
+                let mut value = value.unsigned_abs();
+                let mut magnitude = vec![];
+                while value > 0 {
+                    magnitude.push(value as u32);
+                    value >>= 32;
+                }
+                (self.is_negative(), magnitude)
+            }
+            Repr::Large {
+                negative,
+                magnitude,
+            } => (*negative, magnitude.clone()),
+        }
+    }
+
+    fn from_parts(negative: bool, mut magnitude: Vec<u32>) -> BigInt {
+        while magnitude.last() == Some(&0) {
+            magnitude.pop();
+        }
+        if magnitude.len() <= 4 {
+            let value = magnitude
+                .iter()
+                .rev()
+                .fold(0_u128, |value, &limb| value << 32 | limb as u128);
+            if !negative && value <= i128::MAX as u128 {
+                return BigInt(Repr::Small(value as i128));
+            }
+            if negative && value <= i128::MIN.unsigned_abs() {
+                return BigInt(Repr::Small((value as i128).wrapping_neg()));
+            }
+        }
+        BigInt(Repr::Large {
+            negative,
+            magnitude,
+        })
+    }
+}
+
+fn compare_magnitudes(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0_u64;
+    for idx in 0..a.len().max(b.len()) {
+        let sum = *a.get(idx).unwrap_or(&0) as u64 + *b.get(idx).unwrap_or(&0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+    result.push(carry as u32);
+    result
+}
+
+/// Requires `a >= b`.
+fn sub_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0_i64;
+    for (idx, &limb) in a.iter().enumerate() {
+        let mut difference = limb as i64 - *b.get(idx).unwrap_or(&0) as i64 - borrow;
+        borrow = (difference < 0) as i64;
+        difference += borrow << 32;
+        result.push(difference as u32);
+    }
+    result
+}
+
+fn mul_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0_u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0_u64;
+        for (j, &y) in b.iter().enumerate() {
+            let product = x as u64 * y as u64 + result[i + j] as u64 + carry;
+            result[i + j] = product as u32;
+            carry = product >> 32;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+    result
+}
+
+fn div_rem_magnitude(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0_u32; a.len()];
+    let mut remainder = 0_u64;
+    for idx in (0..a.len()).rev() {
+        let current = remainder << 32 | a[idx] as u64;
+        quotient[idx] = (current / divisor as u64) as u32;
+        remainder = current % divisor as u64;
+    }
+    (quotient, remainder as u32)
+}
+
+macro_rules! impl_big_int_from {
+    ($($t:ty),*) => {$(
+        impl From<$t> for BigInt {
+            fn from(value: $t) -> BigInt {
+                BigInt(Repr::Small(value as i128))
+            }
+        }
+    )*};
+}
+
+impl_big_int_from!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl Add for BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: BigInt) -> BigInt {
+        if let (Repr::Small(a), Repr::Small(b)) = (&self.0, &other.0) {
+            if let Some(sum) = a.checked_add(*b) {
+                return BigInt::from(sum);
+            }
+        }
+
+        let ((a_negative, a), (b_negative, b)) = (self.parts(), other.parts());
+        if a_negative == b_negative {
+            return BigInt::from_parts(a_negative, add_magnitudes(&a, &b));
+        }
+        match compare_magnitudes(&a, &b) {
+            Ordering::Less => BigInt::from_parts(b_negative, sub_magnitudes(&b, &a)),
+            _ => BigInt::from_parts(a_negative, sub_magnitudes(&a, &b)),
+        }
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        match self.0 {
+            Repr::Small(value) => match value.checked_neg() {
+                Some(negated) => BigInt::from(negated),
+                None => BigInt::from_parts(false, self.parts().1),
+            },
+            Repr::Large {
+                negative,
+                magnitude,
+            } => BigInt::from_parts(!negative, magnitude),
+        }
+    }
+}
+
+impl Sub for BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: BigInt) -> BigInt {
+        self + -other
+    }
+}
+
+impl Mul for BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: BigInt) -> BigInt {
+        if let (Repr::Small(a), Repr::Small(b)) = (&self.0, &other.0) {
+            if let Some(product) = a.checked_mul(*b) {
+                return BigInt::from(product);
+            }
+        }
+
+        let ((a_negative, a), (b_negative, b)) = (self.parts(), other.parts());
+        BigInt::from_parts(a_negative != b_negative, mul_magnitudes(&a, &b))
+    }
+}
+
+impl Sum for BigInt {
+    fn sum<I: Iterator<Item = BigInt>>(iter: I) -> BigInt {
+        iter.fold(BigInt::zero(), Add::add)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        if let (Repr::Small(a), Repr::Small(b)) = (&self.0, &other.0) {
+            return a.cmp(b);
+        }
+
+        let ((a_negative, a), (b_negative, b)) = (self.parts(), other.parts());
+        match (a_negative, b_negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitudes(&a, &b),
+            (true, true) => compare_magnitudes(&b, &a),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Repr::Large {
+            negative,
+            magnitude,
+        } = &self.0
+        else {
+            return write!(f, "{}", self.to_i128().unwrap());
+        };
+
+        let mut chunks = vec![];
+        let mut magnitude = magnitude.clone();
+        while magnitude.iter().any(|&limb| limb != 0) {
+            let (quotient, chunk) = div_rem_magnitude(&magnitude, 1_000_000_000);
+            chunks.push(chunk);
+            magnitude = quotient;
+        }
+
+        let digits = chunks
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(idx, chunk)| match idx {
+                0 => chunk.to_string(),
+                _ => format!("{:09}", chunk),
+            })
+            .join("");
+        f.pad_integral(!negative, "", &digits)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseBigIntError;
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<BigInt, ParseBigIntError> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseBigIntError);
+        }
+
+        let magnitude = digits.bytes().fold(BigInt::zero(), |value, digit| {
+            value * BigInt::from(10) + BigInt::from(digit - b'0')
+        });
+        Ok(if negative { -magnitude } else { magnitude })
+    }
+}
+
+/// Circular sequence split into blocks of roughly `√n` elements, so locating, reading and
+/// moving an element costs `O(√n)` instead of shifting the whole sequence.
+///
+/// Elements are addressed either by their current position or by their original index,
+/// i.e. their position in the `Vec` the list was created from.
+#[derive(Debug, Clone)]
+pub struct CircularList<T> {
+    values: Vec<T>,
+    blocks: Vec<Vec<usize>>,
+    block_of: Vec<usize>,
+    block_size: usize,
+}
+
+impl<T> CircularList<T> {
+    pub fn new(values: Vec<T>) -> Self {
+        let block_size = (values.len() as f64).sqrt().ceil().max(1.0) as usize;
+        let mut list = CircularList {
+            blocks: vec![(0..values.len()).collect()],
+            block_of: vec![0; values.len()],
+            values,
+            block_size,
+        };
+        list.rebalance();
+        list
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Value of the element that started at `original`.
+    pub fn value(&self, original: usize) -> &T {
+        &self.values[original]
+    }
+
+    /// Element currently at `position`, wrapping around the end of the list.
+    pub fn get(&self, position: usize) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut position = position % self.len();
+        for block in &self.blocks {
+            if position < block.len() {
+                return Some(&self.values[block[position]]);
+            }
+            position -= block.len();
+        }
+        unreachable!()
+    }
+
+    /// Current position of the element that started at `original`.
+    pub fn position_of(&self, original: usize) -> usize {
+        let block = self.block_of[original];
+        let offset = self.blocks[block]
+            .iter()
+            .position(|&idx| idx == original)
+            .unwrap();
+        self.blocks[..block].iter().map(Vec::len).sum::<usize>() + offset
+    }
+
+    /// Moves the element that started at `original` by `offset` steps, where stepping past
+    /// either end wraps around the other `len() - 1` elements.
+    pub fn move_by(&mut self, original: usize, offset: isize) {
+        if self.len() < 2 {
+            return;
+        }
+
+        let position = self.position_of(original);
+        let block = self.block_of[original];
+        self.blocks[block].retain(|&idx| idx != original);
+
+        let others = (self.len() - 1) as isize;
+        let mut target = (position as isize + offset).rem_euclid(others) as usize;
+        let mut block = 0;
+        while target > self.blocks[block].len() {
+            target -= self.blocks[block].len();
+            block += 1;
+        }
+        self.blocks[block].insert(target, original);
+        self.block_of[original] = block;
+
+        if self.blocks[block].len() > 2 * self.block_size {
+            self.rebalance();
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.blocks
+            .iter()
+            .flatten()
+            .map(move |&original| &self.values[original])
+    }
+
+    fn rebalance(&mut self) {
+        let order = self.blocks.concat();
+        self.blocks = order
+            .chunks(self.block_size)
+            .map(<[usize]>::to_vec)
+            .collect_vec();
+        if self.blocks.is_empty() {
+            self.blocks.push(vec![]);
+        }
+        for (block, originals) in self.blocks.iter().enumerate() {
+            for &original in originals {
+                self.block_of[original] = block;
+            }
+        }
+    }
+}
+
+/// A puzzle that advances in discrete steps, so `cargo solve NN -- --debug` can step through it,
+/// break on a condition and rewind.
+pub trait Simulation {
+    /// What `restore` needs to put the simulation back as it was.
+    type Snapshot: Clone;
+
+    /// The breakpoint conditions `holds` understands, for the debugger's help.
+    const CONDITIONS: &'static str;
+
+    /// Advances one step, or returns `false` if there is nothing left to simulate.
+    fn step(&mut self) -> bool;
+
+    /// One line summing up the current state.
+    fn state(&self) -> String;
+
+    /// A picture of the current state.
+    fn render(&self) -> String;
+
+    fn snapshot(&self) -> Self::Snapshot;
+
+    fn restore(&mut self, snapshot: Self::Snapshot);
+
+    /// Whether a breakpoint `condition` (e.g. `elf 3,4`) holds after the last step, or `None`
+    /// if it is not one this simulation understands.
+    fn holds(&self, _condition: &str) -> Option<bool> {
+        None
+    }
+}
+
+/// Draws a grid with one character per cell, first row at the top.
+pub fn render_grid<T>(rows: &[Vec<T>], cell: impl Fn(&T) -> char) -> String {
+    rows.iter()
+        .map(|row| row.iter().map(&cell).collect::<String>())
+        .join("\n")
+}
+
+/// Heatmap shades, from nothing to the most.
+pub const SHADES: [char; 10] = [' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+/// The shade of `value` on a heatmap whose brightest cell is `max`. Only zero is blank.
+pub fn shade(value: usize, max: usize) -> char {
+    match value {
+        0 => SHADES[0],
+        _ if value >= max => SHADES[SHADES.len() - 1],
+        _ => SHADES[1 + (value * (SHADES.len() - 1) - 1) / max],
+    }
+}
+
+/// Lines of a streamed input, read one at a time. A line that cannot be read (e.g. is not UTF-8)
+/// is `None`, which streaming solvers pass on as no answer.
+pub fn stream_lines(input: impl BufRead) -> impl Iterator<Item = Option<String>> {
+    input.lines().map(|line| line.ok())
+}
+
+/// Bytes of a streamed input, read one buffer at a time; `None` where reading failed.
+pub fn stream_bytes(input: impl BufRead) -> impl Iterator<Item = Option<u8>> {
+    input.bytes().map(|byte| byte.ok())
+}
+
+/// Small deterministic PRNG (SplitMix64), so a generated input depends on nothing but its seed.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n`; `n` must be positive.
+    pub fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// Uniform in `low..=high`.
+    pub fn between(&mut self, low: isize, high: isize) -> isize {
+        low + self.below((high - low) as usize + 1) as isize
+    }
+
+    /// `true` with probability `numerator / denominator`.
+    pub fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.below(idx + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ints_iter() {
+        let line = "Sensor at x=2, y=-18: closest beacon is at x=-2, y=15";
+        let ints: Result<Vec<isize>, _> = line.ints_iter().collect();
+        assert_eq!(ints, Ok(vec![2, -18, -2, 15]));
+
+        let ints: Result<Vec<usize>, _> = "Starting items: 79, 98".ints_iter().collect();
+        assert_eq!(ints, Ok(vec![79, 98]));
+
+        let ints: Result<Vec<i32>, _> = "2-4,6-8".ints_iter().collect();
+        assert_eq!(ints, Ok(vec![2, 4, 6, 8]));
+
+        let ints: Result<Vec<i64>, _> = "a--5 -b -".ints_iter().collect();
+        assert_eq!(ints, Ok(vec![-5]));
+
+        let ints: Result<Vec<u8>, _> = b"1,-2,3".ints_iter().collect();
+        assert_eq!(ints, Ok(vec![1, 2, 3]));
+
+        let ints: Vec<Result<u8, _>> = "1,300,3".ints_iter().collect();
+        assert_eq!(ints, [Ok(1), Err(IntsError::Overflow { offset: 2 }), Ok(3)]);
+    }
+
+    #[test]
+    fn test_ints() {
+        assert_eq!("1,-2,3".ints::<3>(), Ok([1_isize, -2, 3]));
+        assert_eq!(
+            "1,2".ints::<3>(),
+            Err::<[isize; 3], _>(IntsError::Count {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            "1,2,3,4".ints::<3>(),
+            Err::<[isize; 3], _>(IntsError::Count {
+                expected: 3,
+                found: 4
+            })
+        );
+        assert_eq!(
+            "x=300".ints::<1>(),
+            Err::<[u8; 1], _>(IntsError::Overflow { offset: 2 })
+        );
+        assert_eq!("-128".ints::<1>(), Ok([i8::MIN]));
+    }
+
+    #[test]
+    fn test_big_int() {
+        let big: BigInt = "-340282366920938463463374607431768211457".parse().unwrap();
+        assert!(matches!(big.0, Repr::Large { .. }));
+        assert_eq!(big.to_string(), "-340282366920938463463374607431768211457");
+        // a value back in range is small again, and zero is never negative.
+        assert_eq!(big.clone() - big.clone(), BigInt::zero());
+        assert_eq!(
+            BigInt::from_parts(true, vec![0, 0, 0, 0, 0]),
+            BigInt::zero()
+        );
+        let power: BigInt = "340282366920938463463374607431768211456".parse().unwrap();
+        assert!(matches!((big + power).0, Repr::Small(-1)));
+
+        let max = BigInt::from(i128::MAX);
+        let sum = max.clone() + BigInt::from(1);
+        assert_eq!(sum.to_string(), "170141183460469231731687303715884105728");
+        assert_eq!(sum - BigInt::from(1), max);
+
+        let square = max.clone() * max.clone();
+        assert_eq!(
+            square.to_string(),
+            "28948022309329048855892746252171976962977213799489202546401021394546514198529"
+        );
+        assert!(-square.clone() < BigInt::from(i128::MIN));
+        assert!(square > max);
+
+        let (quotient, remainder) = (-max.clone() - BigInt::from(10)).div_rem_euclid(5);
+        assert_eq!(remainder, 3);
+        assert_eq!(
+            quotient * BigInt::from(5) + BigInt::from(3),
+            -max - BigInt::from(10)
+        );
+    }
+
+    #[test]
+    fn test_circular_list() {
+        let values = (0..50).map(|n| (n * 37 % 101) - 50).collect_vec();
+        let mut list = CircularList::new(values.clone());
+        let mut naive = (0..values.len()).collect_vec();
+
+        for _round in 0..3 {
+            for (original, &offset) in values.iter().enumerate() {
+                let position = naive.iter().position(|&idx| idx == original).unwrap();
+                naive.remove(position);
+                let target = (position as isize + offset).rem_euclid(naive.len() as isize) as usize;
+                naive.insert(target, original);
+
+                list.move_by(original, offset);
+                assert_eq!(list.position_of(original), target);
+            }
+        }
+
+        let expected = naive.iter().map(|&idx| values[idx]).collect_vec();
+        assert_eq!(list.iter().copied().collect_vec(), expected);
+        assert_eq!(list.get(values.len() + 3), Some(&expected[3]));
+    }
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let draws = (0..100).map(|_| a.below(10)).collect_vec();
+        assert_eq!(draws, (0..100).map(|_| b.below(10)).collect_vec());
+        assert!(draws.iter().all(|draw| *draw < 10));
+        assert!((0..10).all(|n| draws.contains(&n)));
+
+        assert!((0..100).all(|_| (-3..=3).contains(&a.between(-3, 3))));
+
+        let mut items = (0..20).collect_vec();
+        a.shuffle(&mut items);
+        assert_eq!(
+            items.iter().copied().sorted().collect_vec(),
+            (0..20).collect_vec()
+        );
+    }
+}
@@ -0,0 +1,248 @@
+/*
+ * Reads the block letters some puzzles draw instead of printing their answer.
+ */
+use std::fmt;
+
+use itertools::Itertools;
+
+/// A block font: every letter is `width` x `height` pixels, with `spacing` blank columns
+/// before the next one.
+#[derive(Debug, Clone, Copy)]
+pub struct Font {
+    pub width: usize,
+    pub height: usize,
+    pub spacing: usize,
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+/// The 4x6 font of the 2016, 2019, 2021 and 2022 screens (e.g. 2022 day 10).
+pub const SMALL: Font = Font {
+    width: 4,
+    height: 6,
+    spacing: 1,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        // five wide, its last column falls in the spacing.
+        ('Y', &["#...", "#...", ".#.#", "..#.", "..#.", "..#."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+/// The 6x10 font of the 2018 sky message (2018 day 10).
+#[rustfmt::skip]
+pub const LARGE: Font = Font {
+    width: 6,
+    height: 10,
+    spacing: 2,
+    glyphs: &[
+        ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+        ('B', &["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+        ('C', &[".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+        ('E', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('F', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('G', &[".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+        ('H', &["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+        ('J', &["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+        ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+        ('L', &["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+        ('P', &["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('R', &["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+        ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+        ('Z', &["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+    ],
+};
+
+impl Font {
+    /// The letter drawn by `bitmap`, rows of `#` and `.` separated by newlines.
+    fn letter(&self, bitmap: &str) -> Option<char> {
+        self.glyphs
+            .iter()
+            .find(|(_, rows)| rows.iter().join("\n") == bitmap)
+            .map(|&(letter, _)| letter)
+    }
+
+    /// The letters this font knows.
+    pub fn letters(&self) -> impl Iterator<Item = char> {
+        self.glyphs.iter().map(|&(letter, _)| letter)
+    }
+
+    /// Draws `text` in this font, or `None` if it has a letter the font does not know.
+    pub fn draw(&self, text: &str) -> Option<String> {
+        let glyphs: Vec<_> = text
+            .chars()
+            .map(|c| self.glyphs.iter().find(|(letter, _)| *letter == c))
+            .collect::<Option<_>>()?;
+        let gap = ".".repeat(self.spacing);
+        Some(
+            (0..self.height)
+                .map(|row| glyphs.iter().map(|(_, rows)| rows[row]).join(&gap))
+                .join("\n"),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// The lit pixels span a different number of rows than the font's letters.
+    Height { expected: usize, found: usize },
+    /// Every glyph that is no letter of the font, by position, with its bitmap.
+    Unrecognised(Vec<(usize, String)>),
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Height { expected, found } => {
+                write!(
+                    f,
+                    "expected letters {} pixels high, found {}",
+                    expected, found
+                )
+            }
+            OcrError::Unrecognised(glyphs) => {
+                let glyphs = glyphs
+                    .iter()
+                    .map(|(position, bitmap)| {
+                        format!("glyph {} is not a letter:\n{}", position, bitmap)
+                    })
+                    .join("\n");
+                write!(f, "{}", glyphs)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Reads the letters drawn in `image`, where `#` (or `█`) is a lit pixel and anything else is
+/// dark. Blank margins are ignored: the letters are found wherever they are, as long as they
+/// sit on one line and keep to the font's spacing.
+pub fn ocr(image: &str, font: &Font) -> Result<String, OcrError> {
+    let pixels = image
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#' || c == '█').collect_vec())
+        .collect_vec();
+    let lit_rows = pixels
+        .iter()
+        .positions(|row| row.contains(&true))
+        .collect_vec();
+    let (Some(&top), Some(&bottom)) = (lit_rows.first(), lit_rows.last()) else {
+        return Ok(String::new());
+    };
+    let rows = &pixels[top..=bottom];
+    if rows.len() != font.height {
+        return Err(OcrError::Height {
+            expected: font.height,
+            found: rows.len(),
+        });
+    }
+    let lit = |row: &[bool], column: isize| column >= 0 && row.get(column as usize) == Some(&true);
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0) as isize;
+    let left = (0..columns)
+        .find(|&column| rows.iter().any(|row| lit(row, column)))
+        .unwrap();
+    let right = (0..columns)
+        .rfind(|&column| rows.iter().any(|row| lit(row, column)))
+        .unwrap();
+
+    // a letter may start with blank columns (e.g. `I`), so its cell can begin left of the
+    // first lit pixel: every start that keeps that pixel inside the first letter is tried.
+    let cell = font.width + font.spacing;
+    let read = |start: isize| -> Result<String, Vec<(usize, String)>> {
+        let (letters, unrecognised): (Vec<_>, Vec<_>) = (start..=right)
+            .step_by(cell)
+            .enumerate()
+            .map(|(position, column)| {
+                let bitmap = rows
+                    .iter()
+                    .map(|row| {
+                        (column..column + font.width as isize)
+                            .map(|c| if lit(row, c) { '#' } else { '.' })
+                            .collect::<String>()
+                    })
+                    .join("\n");
+                font.letter(&bitmap).ok_or((position, bitmap))
+            })
+            .partition_result();
+        match unrecognised.is_empty() {
+            true => Ok(letters.into_iter().collect()),
+            false => Err(unrecognised),
+        }
+    };
+    let mut fewest: Option<Vec<(usize, String)>> = None;
+    for start in (left - font.width as isize + 1..=left).rev() {
+        match read(start) {
+            Ok(text) => return Ok(text),
+            Err(unrecognised) => {
+                if fewest
+                    .as_ref()
+                    .is_none_or(|fewest| unrecognised.len() < fewest.len())
+                {
+                    fewest = Some(unrecognised);
+                }
+            }
+        }
+    }
+    Err(OcrError::Unrecognised(fewest.unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ocr() {
+        let image = SMALL.draw("IJKLPRY").unwrap();
+        assert_eq!(ocr(&image, &SMALL), Ok("IJKLPRY".to_string()));
+        // a 40x6 screen, with a blank column after the last letter.
+        let screen = SMALL.draw("ZABCEFGH").unwrap().replace('.', " ");
+        let screen = screen.lines().map(|row| format!("{} ", row)).join("\n");
+        assert_eq!(ocr(&screen, &SMALL), Ok("ZABCEFGH".to_string()));
+
+        let sky = LARGE.draw("JHXNZ").unwrap().replace('#', "█");
+        let sky = format!(
+            "\n\n{}\n",
+            sky.lines().map(|row| format!("...{}.", row)).join("\n")
+        );
+        assert_eq!(ocr(&sky, &LARGE), Ok("JHXNZ".to_string()));
+        assert_eq!(ocr("....\n....", &LARGE), Ok(String::new()));
+        assert_eq!(
+            ocr(&sky, &SMALL),
+            Err(OcrError::Height {
+                expected: 6,
+                found: 10
+            })
+        );
+
+        // the bottom of the `O` is chipped.
+        let mut image = SMALL.draw("HOLA").unwrap().into_bytes();
+        image[5 * 20 + 7] = b'.';
+        image[5 * 20 + 8] = b'#';
+        let image = String::from_utf8(image).unwrap();
+        let error = ocr(&image, &SMALL).unwrap_err();
+        assert_eq!(
+            error,
+            OcrError::Unrecognised(vec![(1, ".##.\n#..#\n#..#\n#..#\n#..#\n.#.#".to_string())])
+        );
+        assert_eq!(
+            error.to_string(),
+            "glyph 1 is not a letter:\n.##.\n#..#\n#..#\n#..#\n#..#\n.#.#"
+        );
+    }
+}
@@ -3,9 +3,23 @@
  * There is no need to edit this file unless you want to change template functionality.
  * Prefer `./helpers.rs` if you want to extract code from your solutions.
  */
+use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::fs;
+use std::io::{self, BufRead, BufReader};
+use std::process;
+use std::str::FromStr;
 
+use itertools::Itertools;
+
+#[cfg(feature = "checkpoint")]
+pub mod checkpoint;
+pub mod days;
+pub mod debugger;
+pub mod differential;
+pub mod fuzz;
+pub mod generators;
 pub mod helpers;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -14,25 +28,32 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 #[macro_export]
 macro_rules! solve {
-    ($part:expr, $solver:ident, $input:expr) => {{
+    ($part:expr, $solver:ident, $input:expr, $params:expr) => {{
+        $crate::solve!($part, |input: &str| $solver(input, $params), $input)
+    }};
+    ($part:expr, $solver:expr, $input:expr) => {{
         use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
         use std::fmt::Display;
         use std::time::Instant;
 
         fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
             let timer = Instant::now();
-            let result = func(input);
+            // under the `checked` profile, arithmetic overflow panics instead of wrapping.
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| func(input)));
             let elapsed = timer.elapsed();
             match result {
-                Some(result) => {
+                Ok(Some(result)) => {
                     println!(
                         "{} {}(elapsed: {:.2?}){}",
                         result, ANSI_ITALIC, elapsed, ANSI_RESET
                     );
                 }
-                None => {
+                Ok(None) => {
                     println!("not solved.")
                 }
+                Err(panic) => {
+                    println!("error: {}", advent_of_code::panic_message(&*panic));
+                }
             }
         }
 
@@ -41,6 +62,222 @@ macro_rules! solve {
     }};
 }
 
+/// Solves one part of a streaming day over `stream`, skipping parts it does not solve.
+#[macro_export]
+macro_rules! solve_stream {
+    ($part:expr, $solver:ident, $stream:expr) => {{
+        if $stream.solves($part) {
+            $crate::solve!($part, |_: &str| $solver($stream.open()), $stream.path());
+        }
+    }};
+}
+
+/// Where a day with streaming solvers reads its input with `--stream <path>`, one line at a time
+/// instead of all at once (e.g. `cat big.txt | cargo solve 06 -- --stream -`). Stdin (`-`) can
+/// only be read once, so it is solved for the part given by `--part`, 1 by default.
+#[derive(Debug, Clone)]
+pub struct Stream {
+    path: String,
+    part: Option<u8>,
+}
+
+impl Stream {
+    pub fn from_args() -> Option<Stream> {
+        let mut args = pico_args::Arguments::from_env();
+        let path: String = args
+            .opt_value_from_str("--stream")
+            .expect("could not read --stream argument")?;
+        let part = args
+            .opt_value_from_str("--part")
+            .expect("could not read --part argument");
+        let part = match path.as_str() {
+            "-" => Some(part.unwrap_or(1)),
+            _ => part,
+        };
+        Some(Stream { path, part })
+    }
+
+    pub fn solves(&self, part: u8) -> bool {
+        self.part.is_none_or(|only| only == part)
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn open(&self) -> Box<dyn BufRead> {
+        match self.path.as_str() {
+            "-" => Box::new(io::stdin().lock()),
+            path => Box::new(BufReader::new(
+                fs::File::open(path).expect("could not open input file"),
+            )),
+        }
+    }
+}
+
+/// A puzzle constant a day reads: its key, the value it takes when not given and whether a
+/// given value parses as its type. Each day declares its own in a `PARAMS` table.
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub key: &'static str,
+    pub default: &'static str,
+    parses: fn(&str) -> bool,
+}
+
+fn parses<T: FromStr>(value: &str) -> bool {
+    value.parse::<T>().is_ok()
+}
+
+impl Param {
+    pub const fn new<T: FromStr>(key: &'static str, default: &'static str) -> Param {
+        Param {
+            key,
+            default,
+            parses: parses::<T>,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    /// A key the day does not declare, with those it does.
+    Unknown {
+        key: String,
+        known: Vec<&'static str>,
+    },
+    /// A value that does not parse as its param's type.
+    Invalid { key: String, value: String },
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamError::Unknown { key, known } if known.is_empty() => {
+                write!(f, "unknown param `{}`: this day has none", key)
+            }
+            ParamError::Unknown { key, known } => {
+                write!(
+                    f,
+                    "unknown param `{}`, expected one of: {}",
+                    key,
+                    known.join(", ")
+                )
+            }
+            ParamError::Invalid { key, value } => {
+                write!(f, "invalid value `{}` for param `{}`", value, key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// Values for a day's declared params, overridable with `--param key=value` (e.g. `cargo solve
+/// 11 -- --param rounds_one=50`) or passed directly from tests.
+#[derive(Debug, Clone, Default)]
+pub struct Params(HashMap<String, String>);
+
+impl Params {
+    /// The `--param` options, checked against the day's `declared` params. Exits with the error
+    /// if they do not match.
+    pub fn from_args(declared: &[Param]) -> Params {
+        let params = Params::from_arguments(&mut pico_args::Arguments::from_env());
+        if let Err(error) = params.check(declared) {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+        params
+    }
+
+    /// Consumes the `--param` options from `args`, leaving the rest to the caller, who checks
+    /// them. The checkpoint params also have options of their own, `--checkpoint-every N` and
+    /// `--resume <file>`.
+    pub fn from_arguments(args: &mut pico_args::Arguments) -> Params {
+        let values: Vec<String> = args
+            .values_from_str("--param")
+            .expect("could not read --param arguments");
+
+        let mut params = Params(
+            values
+                .iter()
+                .map(|value| {
+                    let (key, value) = value
+                        .split_once('=')
+                        .unwrap_or_else(|| panic!("expected --param key=value, got `{}`", value));
+                    (key.to_string(), value.to_string())
+                })
+                .collect(),
+        );
+        for (option, key) in [
+            ("--checkpoint-every", "checkpoint_every"),
+            ("--resume", "resume"),
+        ] {
+            let value: Option<String> = args
+                .opt_value_from_str(option)
+                .unwrap_or_else(|_| panic!("could not read {} argument", option));
+            if let Some(value) = value {
+                params = params.with(key, value);
+            }
+        }
+        params
+    }
+
+    pub fn with(mut self, key: &str, value: impl ToString) -> Params {
+        self.0.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// These params, plus those of `defaults` they do not set.
+    pub fn with_defaults(mut self, defaults: Params) -> Params {
+        for (key, value) in defaults.0 {
+            self.0.entry(key).or_insert(value);
+        }
+        self
+    }
+
+    /// Checks that every param given is one of `declared` and parses as its type.
+    pub fn check(&self, declared: &[Param]) -> Result<(), ParamError> {
+        for (key, value) in self.0.iter().sorted() {
+            let Some(param) = declared.iter().find(|param| param.key == key) else {
+                return Err(ParamError::Unknown {
+                    key: key.clone(),
+                    known: declared.iter().map(|param| param.key).collect(),
+                });
+            };
+            if !(param.parses)(value) {
+                return Err(ParamError::Invalid {
+                    key: key.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The value of `key`, one of the `declared` params, or its default if it is not given.
+    /// Params that have not been through `check` panic if they do not parse.
+    pub fn get<T: FromStr>(&self, declared: &[Param], key: &str) -> T {
+        let param = declared
+            .iter()
+            .find(|param| param.key == key)
+            .unwrap_or_else(|| panic!("param `{}` is not declared", key));
+        let value = self.0.get(key).map_or(param.default, String::as_str);
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid value `{}` for param `{}`", value, key))
+    }
+}
+
+pub fn panic_message(panic: &(dyn std::any::Any + Send)) -> &str {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message
+    } else {
+        "solver panicked"
+    }
+}
+
 pub fn read_file(folder: &str, day: u8) -> String {
     let cwd = env::current_dir().unwrap();
 
@@ -95,6 +332,40 @@ macro_rules! assert_approx_eq {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_params() {
+        let declared = [
+            Param::new::<usize>("rounds", "20"),
+            Param::new::<isize>("row", "-10"),
+        ];
+        let params = Params::default().with("rounds", 50);
+        assert_eq!(params.check(&declared), Ok(()));
+        assert_eq!(params.get::<usize>(&declared, "rounds"), 50);
+        assert_eq!(params.get::<isize>(&declared, "row"), -10);
+
+        let params = params.with("row", "ten");
+        assert_eq!(
+            params.check(&declared),
+            Err(ParamError::Invalid {
+                key: "row".to_string(),
+                value: "ten".to_string()
+            })
+        );
+        let error = Params::default()
+            .with("rocks", 5)
+            .check(&declared)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown param `rocks`, expected one of: rounds, row"
+        );
+        let error = Params::default().with("rocks", 5).check(&[]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown param `rocks`: this day has none"
+        );
+    }
+
     #[test]
     fn test_parse_exec_time() {
         assert_approx_eq!(
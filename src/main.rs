@@ -6,12 +6,18 @@ use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 use std::process::Command;
 
 fn main() {
+    // `cargo all --checked` runs every day with overflow checks enabled.
+    let profile = match pico_args::Arguments::from_env().contains("--checked") {
+        true => "--profile=checked",
+        false => "--release",
+    };
+
     let total: f64 = (1..=25)
         .map(|day| {
             let day = format!("{:02}", day);
 
             let cmd = Command::new("cargo")
-                .args(["run", "--release", "--bin", &day])
+                .args(["run", profile, "--bin", &day])
                 .output()
                 .unwrap();
 